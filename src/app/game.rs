@@ -1,122 +1,672 @@
-use std::{io, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs, io,
+    path::Path,
+    rc::Rc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use crossterm::event::Event;
-use ratatui::Terminal;
+use crossterm::event::{Event, KeyEvent};
+use ratatui::{Frame, Terminal, layout::Size};
 
 use crate::{
-    app::input::{InputManager, UserAction},
-    domain::{EnemyConfig, GameConfig, World},
-    tui::{FileSelectAction, UiAction, UiManager},
+    app::{
+        ghost::Ghost,
+        input::{InputManager, UserAction},
+        lesson::{LessonAction, LessonSession},
+        replay::{Replay, ReplayAction, ReplayPlayer, replay_dir},
+        simulation::Simulation,
+    },
+    domain::{
+        Achievements, Challenge, ChallengeRun, DRILLS, Drill, GameConfig, HighScore, HighScores,
+        LESSONS, LessonPhase, LessonProgress, RANDOM_SOURCE, Scenario, ScoreKey, ScoreReason,
+        SharedClock, SystemClock, World, is_scenario_path,
+    },
+    tui::{
+        ChallengeSelectAction, DrillSelectAction, FileSelectAction, GhostView, HighScoresAction,
+        LessonSelectAction, LessonView, ReplaySelectAction, ReplayView, ResultsAction, ResultsView,
+        ScreenshotFormat, UiAction, UiManager, handle_results_key,
+    },
 };
 
-const INTERVAL: Duration = Duration::from_millis(10);
+/// Time between two fixed-rate updates of the game loop
+pub(super) const INTERVAL: Duration = Duration::from_millis(10);
 
 /// Represents the current mode of the game.
-#[derive(Default)]
+#[derive(Clone, Copy, Default)]
 enum GameMode {
     /// Main menu selection
     #[default]
     Menu,
+    /// Drill selection screen
+    DrillSelect,
     /// File selection screen
     FileSelect,
     /// Main game
     Game,
+    /// Challenge selection screen
+    ChallengeSelect,
+    /// A running buffer transformation challenge
+    Challenge,
+    /// Lesson selection screen
+    LessonSelect,
+    /// A running lesson
+    Lesson,
+    /// Replay selection screen
+    ReplaySelect,
+    /// A replay being played back
+    Replay,
+    /// Results of the round that just ended
+    Results,
+    /// High score tables
+    HighScores,
+}
+
+/// Outcome of the round that just ended, as shown on the results screen.
+struct RoundResults {
+    /// High score table the round was played for
+    key: ScoreKey,
+    score: usize,
+    keystrokes: usize,
+    accuracy: usize,
+    /// Points earned, broken down by reason
+    points: BTreeMap<ScoreReason, usize>,
+    /// Rank the round made in its table, if any
+    rank: Option<usize>,
+    /// Screen shown once the results are dismissed
+    next: GameMode,
 }
 
 /// Main game structure orchestrating state, input, and UI.
-#[derive(Default)]
 pub struct Game {
     mode: GameMode,
-    world: World,
+    /// Round being played
+    round: Simulation,
+    /// When the round started, to keep it in step with real time
+    round_started: Instant,
+    /// Recorded run raced in the round, if any
+    ghost: Option<Ghost>,
+    /// True while playing a scenario level with enemies left
+    level: bool,
+    /// Achievements unlocked this session, following every round
+    achievements: Rc<RefCell<Achievements>>,
     input: InputManager,
     ui: UiManager,
+    /// Drill of the next rounds, if any
+    drill: Option<Drill>,
+    /// Challenge being played, if any
+    challenge: Option<ChallengeRun>,
+    /// Lesson being played, if any
+    lesson: Option<LessonSession>,
+    /// Replay being played back, if any
+    replay: Option<ReplayPlayer>,
+    /// When the last replay frame was drawn
+    last_frame: Instant,
+    /// Results of the last round, while shown
+    results: Option<RoundResults>,
+    /// Saved high scores
+    high_scores: HighScores,
+    /// Saved lesson progress
+    progress: LessonProgress,
+    /// Real time clock pacing rounds, challenges and lessons
+    clock: SharedClock,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Rc::new(SystemClock))
+    }
 }
 
 impl Game {
+    /// Creates a game paced by `clock`.
+    pub fn new(clock: SharedClock) -> Self {
+        let round = Simulation::new(GameConfig::default());
+        Self {
+            mode: GameMode::default(),
+            round_started: clock.now(),
+            ui: UiManager::new(round.clock()),
+            round,
+            ghost: None,
+            level: false,
+            achievements: Rc::default(),
+            input: InputManager::default(),
+            drill: None,
+            challenge: None,
+            lesson: None,
+            replay: None,
+            last_frame: clock.now(),
+            results: None,
+            high_scores: HighScores::default(),
+            progress: LessonProgress::default(),
+            clock,
+        }
+    }
+
     /// Starts a new round by resetting the game state and UI.
     pub fn new_round(&mut self, file_path: Option<String>) {
-        let game_config = GameConfig {
-            enemy: EnemyConfig::default(),
-            file_path,
+        let mut config =
+            GameConfig::for_round(self.drill, file_path).with_difficulty(self.ui.difficulty());
+        if let Some(spawn_rule) = self.ui.spawn_rule() {
+            config = config.with_spawn_rule(spawn_rule);
+        }
+        self.start_round(config);
+        self.ghost = None;
+    }
+
+    /// Starts a round on the seed and code of `replay`, racing the run it recorded.
+    fn race(&mut self, replay: Replay) {
+        self.start_round(replay.config());
+        let mut ghost = Ghost::new(replay);
+        ghost.subscribe(self.ui.ghost_subscriber());
+        self.ghost = Some(ghost);
+    }
+
+    fn start_round(&mut self, config: GameConfig) {
+        let scenario = config.file_path.as_deref().is_some_and(is_scenario_path);
+        self.round = Simulation::new(config);
+        self.round.subscribe(self.achievements.clone());
+        for subscriber in self.ui.round_subscribers() {
+            self.round.subscribe(subscriber);
+        }
+        self.level = scenario && self.round.world().enemies().iter().next().is_some();
+        self.round_started = self.clock.now();
+        self.ui.reset();
+        self.ui.set_clock(self.round.clock());
+    }
+
+    /// Ends the round, saving its replay and score, then shows its results before `next`.
+    ///
+    /// Rounds where no key was pressed go straight to `next`.
+    fn end_round(&mut self, next: GameMode) {
+        self.save_replay();
+        let replay = self.round.replay();
+        if replay.keys.is_empty() {
+            self.mode = next;
+            return;
+        }
+
+        let world = self.round.world();
+        let key = ScoreKey::new(
+            replay.file_path.as_deref(),
+            replay.drill.as_deref(),
+            replay.difficulty,
+        );
+        let score = HighScore {
+            score: world.score(),
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            keystrokes: replay.keys.len(),
+            accuracy: self.round.stats().accuracy(),
+            file: replay
+                .file_path
+                .unwrap_or_else(|| RANDOM_SOURCE.to_string()),
         };
-        self.world = World::new(game_config);
-        self.input.reset();
+        let (total, keystrokes, accuracy) = (score.score, score.keystrokes, score.accuracy);
+        let points = self.round.stats().points.clone();
+
+        // Reload first, another session may have saved scores since
+        self.high_scores = HighScores::load_default();
+        let rank = self.high_scores.record(key.clone(), score);
+        // High scores are best effort, the results are still shown
+        let _ = self.high_scores.save();
+        self.results = Some(RoundResults {
+            key,
+            score: total,
+            keystrokes,
+            accuracy,
+            points,
+            rank,
+            next,
+        });
+        self.mode = GameMode::Results;
+    }
+
+    /// Saves the replay of the round that just ended, if any key was pressed.
+    fn save_replay(&self) {
+        let replay = self.round.replay();
+        if !replay.keys.is_empty() {
+            let _ = replay.save_default();
+        }
+    }
+
+    /// Saves the current frame of the round to `path`, in the format of its extension.
+    fn save_screenshot(&mut self, path: &str, width: u16, height: u16) {
+        let format = ScreenshotFormat::from_path(Path::new(path));
+        let frame = self.ui.screenshot(
+            self.round.world(),
+            &self.round.stats(),
+            self.round.keys_iter(),
+            width,
+            height,
+            format,
+        );
+        let message = match fs::write(path, frame) {
+            Ok(()) => format!("Screenshot saved to {}", path),
+            Err(err) => format!("Screenshot failed: {}", err),
+        };
+        self.ui.notify(message);
+    }
+
+    /// Starts playing back `replay` from its beginning.
+    fn start_replay(&mut self, replay: Replay) {
+        let mut player = ReplayPlayer::new(replay);
+        for subscriber in self.ui.round_subscribers() {
+            player.subscribe(subscriber);
+        }
         self.ui.reset();
+        self.ui.set_clock(player.round().clock());
+        self.replay = Some(player);
+        self.last_frame = self.clock.now();
     }
 
     /// Runs the game loop within the provided terminal.
+    ///
+    /// The world is updated at a fixed rate while something moves, every queued key
+    /// is handled at once, and a frame is only drawn when something changed. Screens
+    /// where nothing moves just wait for the next key.
     pub fn run_in<B: ratatui::backend::Backend>(
         &mut self,
         terminal: &mut Terminal<B>,
     ) -> io::Result<()> {
+        let mut next_update = self.clock.now();
+        let mut redraw = true;
         loop {
-            match self.mode {
-                // Handle menu
-                GameMode::Menu => {
-                    terminal.draw(|f| self.ui.render_menu(f))?;
+            // Catch up on the updates due since the last frame
+            let now = self.clock.now();
+            if self.is_animated() {
+                while next_update <= now {
+                    self.update();
+                    next_update += INTERVAL;
+                    redraw = true;
+                }
+            } else {
+                next_update = now;
+            }
 
-                    if crossterm::event::poll(INTERVAL)?
-                        && let Event::Key(key) = crossterm::event::read()?
-                    {
-                        let action = self.ui.handle_menu_key(key);
-                        match action {
-                            UiAction::StartGame => self.mode = GameMode::FileSelect,
-                            UiAction::Quit => break,
-                            UiAction::Noop => {}
+            if redraw {
+                terminal.draw(|f| self.render(f))?;
+                redraw = false;
+            }
+
+            // Wait for input until the next update is due, or for good if nothing moves
+            let timeout = self
+                .is_animated()
+                .then(|| next_update.saturating_duration_since(self.clock.now()));
+            let size = terminal.size()?;
+            for event in drain_events(timeout)? {
+                match event {
+                    Event::Key(key) => {
+                        if !self.handle_key(key, size) {
+                            return Ok(());
                         }
+                        redraw = true;
                     }
+                    Event::Resize(..) => redraw = true,
+                    _ => {}
                 }
-                // Handle file selection
-                GameMode::FileSelect => {
-                    terminal.draw(|f| self.ui.render_file_select(f))?;
+            }
+        }
+    }
 
-                    if crossterm::event::poll(INTERVAL)?
-                        && let Event::Key(key) = crossterm::event::read()?
-                    {
-                        let action = self.ui.handle_file_select_key(key);
-                        match action {
-                            FileSelectAction::Confirm(path) => {
-                                self.mode = GameMode::Game;
-                                self.new_round(Some(path));
-                            }
-                            FileSelectAction::UseRandom => {
-                                self.mode = GameMode::Game;
-                                self.new_round(None);
-                            }
-                            FileSelectAction::Cancel => self.mode = GameMode::Menu,
-                            FileSelectAction::Noop => {}
-                        }
-                    }
+    /// Returns true while the current screen changes without input.
+    fn is_animated(&self) -> bool {
+        match self.mode {
+            GameMode::Game => true,
+            GameMode::Challenge => self.challenge.as_ref().is_some_and(|run| !run.is_solved()),
+            GameMode::Lesson => self.lesson.as_ref().is_some_and(|lesson| {
+                lesson.phase() == LessonPhase::Demo && !lesson.demo_finished()
+            }),
+            GameMode::Replay => {
+                self.replay
+                    .as_ref()
+                    .is_some_and(|player| !player.is_paused() && !player.is_finished())
+                    || self.ui.is_animating()
+            }
+            GameMode::Menu
+            | GameMode::DrillSelect
+            | GameMode::FileSelect
+            | GameMode::ChallengeSelect
+            | GameMode::LessonSelect
+            | GameMode::ReplaySelect
+            | GameMode::Results
+            | GameMode::HighScores => false,
+        }
+    }
+
+    /// Advances the current screen by one fixed step.
+    fn update(&mut self) {
+        let now = self.clock.now();
+        match self.mode {
+            GameMode::Game => {
+                // Keep the round in step with real time
+                let elapsed = now.duration_since(self.round_started);
+                self.round.advance_to(elapsed);
+                if let Some(ghost) = self.ghost.as_mut() {
+                    ghost.follow(elapsed);
                 }
-                // Main game loop
-                GameMode::Game => {
-                    self.world.tick();
 
-                    // Pull game events from world to be used by other components
-                    let events = self.world.pull_events();
+                if self.level && self.round.world().enemies().iter().next().is_none() {
+                    self.level = false;
+                    self.ui
+                        .notify(format!("Level cleared in {} keys", self.round.keystrokes()));
+                }
+                for achievement in self.achievements.borrow_mut().take_unlocked() {
+                    self.ui.notify(format!("Achievement: {}", achievement));
+                }
+            }
+            GameMode::Replay => {
+                if let Some(player) = self.replay.as_mut() {
+                    player.update(now.duration_since(self.last_frame));
+                }
+                self.last_frame = now;
+            }
+            GameMode::Lesson => {
+                if let Some(lesson) = self.lesson.as_mut() {
+                    lesson.tick();
+                }
+            }
+            _ => {}
+        }
+    }
 
-                    // Render the game UI
-                    terminal.draw(|f| {
-                        self.ui
-                            .render_game(f, &self.world, &events, self.input.keys_iter())
-                    })?;
+    /// Draws the current screen.
+    fn render(&mut self, f: &mut Frame) {
+        match self.mode {
+            GameMode::Menu => self.ui.render_menu(f),
+            GameMode::DrillSelect => self.ui.render_drill_select(f),
+            GameMode::FileSelect => self.ui.render_file_select(f),
+            GameMode::ChallengeSelect => self.ui.render_challenge_select(f),
+            GameMode::LessonSelect => self.ui.render_lesson_select(f, &self.progress),
+            GameMode::ReplaySelect => self.ui.render_replay_select(f),
+            GameMode::HighScores => self.ui.render_high_scores(f, &self.high_scores),
+            GameMode::Results => {
+                if let Some(results) = self.results.as_ref() {
+                    let view = ResultsView {
+                        score: results.score,
+                        keystrokes: results.keystrokes,
+                        accuracy: results.accuracy,
+                        points: &results.points,
+                        key: &results.key,
+                        scores: self.high_scores.top(&results.key),
+                        rank: results.rank,
+                    };
+                    self.ui.render_results(f, view);
+                }
+            }
+            GameMode::Challenge => {
+                if let Some(run) = self.challenge.as_ref() {
+                    self.ui.render_challenge(
+                        f,
+                        run,
+                        self.input.is_inserting(),
+                        self.input.keys_iter(),
+                    );
+                }
+            }
+            GameMode::Lesson => {
+                if let Some(lesson) = self.lesson.as_ref() {
+                    let view = LessonView {
+                        index: lesson.index(),
+                        lesson: lesson.lesson(),
+                        phase: lesson.phase(),
+                        exercise: lesson.exercise(),
+                        demo_finished: lesson.demo_finished(),
+                    };
+                    self.ui.render_lesson(f, view, lesson.keys_iter());
+                }
+            }
+            GameMode::Replay => {
+                if let Some(player) = self.replay.as_ref() {
+                    let view = ReplayView {
+                        position: player.position(),
+                        duration: player.replay().duration(),
+                        speed: player.speed(),
+                        paused: player.is_paused(),
+                        score: player.replay().score,
+                    };
+                    let round = player.round();
+                    self.ui.render_replay(
+                        f,
+                        round.world(),
+                        &round.stats(),
+                        round.keys_iter(),
+                        view,
+                    );
+                }
+            }
+            GameMode::Game => {
+                let ghost = self.ghost.as_ref().map(|ghost| GhostView {
+                    cursor: ghost.cursor(),
+                    score: ghost.score(),
+                });
+                self.ui.render_game(
+                    f,
+                    self.round.world(),
+                    &self.round.stats(),
+                    self.round.keys_iter(),
+                    ghost,
+                );
+            }
+        }
+    }
 
-                    if crossterm::event::poll(INTERVAL)?
-                        && let Event::Key(key) = crossterm::event::read()?
+    /// Handles a key on the current screen, on a terminal of `size`.
+    ///
+    /// Returns false once the player quits the game.
+    fn handle_key(&mut self, key: KeyEvent, size: Size) -> bool {
+        match self.mode {
+            GameMode::Menu => match self.ui.handle_menu_key(key) {
+                UiAction::StartGame => {
+                    self.drill = None;
+                    self.mode = GameMode::FileSelect;
+                }
+                UiAction::Drills => self.mode = GameMode::DrillSelect,
+                UiAction::Challenges => {
+                    self.ui.refresh_challenges();
+                    self.mode = GameMode::ChallengeSelect;
+                }
+                UiAction::Lessons => {
+                    self.progress = LessonProgress::load_default();
+                    self.mode = GameMode::LessonSelect;
+                }
+                UiAction::Replays => {
+                    self.ui.refresh_replays(replay_dir().as_deref());
+                    self.mode = GameMode::ReplaySelect;
+                }
+                UiAction::HighScores => {
+                    self.high_scores = HighScores::load_default();
+                    self.ui.refresh_high_scores(&self.high_scores);
+                    self.mode = GameMode::HighScores;
+                }
+                UiAction::Quit => return false,
+                UiAction::Noop => {}
+            },
+            GameMode::DrillSelect => match self.ui.handle_drill_select_key(key) {
+                DrillSelectAction::Start(index) => {
+                    self.drill = Some(DRILLS[index]);
+                    self.mode = GameMode::FileSelect;
+                }
+                DrillSelectAction::Cancel => self.mode = GameMode::Menu,
+                DrillSelectAction::Noop => {}
+            },
+            GameMode::FileSelect => match self.ui.handle_file_select_key(key) {
+                FileSelectAction::Confirm(path) => {
+                    // Scenarios are checked up front, a broken level is not a random round
+                    if is_scenario_path(&path)
+                        && let Err(err) = Scenario::load(&path)
                     {
-                        let action = self.input.handle_key(key);
-                        match action {
-                            UserAction::Motion((motion, count)) => {
-                                self.world.apply_motion(motion, count);
-                            }
-                            UserAction::Quit => self.mode = GameMode::Menu,
-                            UserAction::NewGame => self.mode = GameMode::FileSelect,
-                            _ => {}
-                        }
+                        self.ui.set_file_error(err.to_string());
+                    } else {
+                        self.mode = GameMode::Game;
+                        self.new_round(Some(path));
+                    }
+                }
+                FileSelectAction::UseRandom => {
+                    self.mode = GameMode::Game;
+                    self.new_round(None);
+                }
+                FileSelectAction::Cancel => self.mode = GameMode::Menu,
+                FileSelectAction::Noop => {}
+            },
+            GameMode::ChallengeSelect => match self.ui.handle_challenge_select_key(key) {
+                ChallengeSelectAction::Start(path) => match Challenge::load(&path) {
+                    Ok(challenge) => {
+                        self.challenge = Some(ChallengeRun::new(challenge, self.clock.clone()));
+                        self.input.reset();
+                        self.input.set_drill(None);
+                        self.input.set_editing(true);
+                        self.mode = GameMode::Challenge;
+                    }
+                    Err(err) => self.ui.set_challenge_error(err.to_string()),
+                },
+                ChallengeSelectAction::Cancel => self.mode = GameMode::Menu,
+                ChallengeSelectAction::Noop => {}
+            },
+            GameMode::Challenge => {
+                let Some(run) = self.challenge.as_mut() else {
+                    self.mode = GameMode::ChallengeSelect;
+                    return true;
+                };
+                run.record_keystroke();
+                match self.input.handle_key(key) {
+                    UserAction::Motion((motion, count)) => run.apply_motion(motion, count),
+                    UserAction::Edit((edit, count)) => run.apply_edit(edit, count),
+                    UserAction::Quit => {
+                        self.challenge = None;
+                        self.input.set_editing(false);
+                        self.mode = GameMode::ChallengeSelect;
                     }
+                    _ => {}
                 }
             }
+            GameMode::LessonSelect => match self.ui.handle_lesson_select_key(key, &self.progress) {
+                LessonSelectAction::Start(index) => {
+                    self.lesson = Some(LessonSession::new(index, self.clock.clone()));
+                    self.mode = GameMode::Lesson;
+                }
+                LessonSelectAction::Cancel => self.mode = GameMode::Menu,
+                LessonSelectAction::Noop => {}
+            },
+            GameMode::Lesson => {
+                let Some(lesson) = self.lesson.as_mut() else {
+                    self.mode = GameMode::LessonSelect;
+                    return true;
+                };
+                match lesson.handle_key(key) {
+                    LessonAction::Completed(grade) => {
+                        self.progress.record(lesson.lesson().id, grade);
+                        // Progress is best effort, the lesson still counts this session
+                        let _ = self.progress.save();
+                    }
+                    LessonAction::Next if lesson.index() + 1 < LESSONS.len() => {
+                        let next = lesson.index() + 1;
+                        self.lesson = Some(LessonSession::new(next, self.clock.clone()));
+                        self.ui.select_lesson(next);
+                    }
+                    LessonAction::Next | LessonAction::Exit => {
+                        self.ui.select_lesson(lesson.index());
+                        self.lesson = None;
+                        self.mode = GameMode::LessonSelect;
+                    }
+                    LessonAction::Noop => {}
+                }
+            }
+            GameMode::ReplaySelect => match self.ui.handle_replay_select_key(key) {
+                ReplaySelectAction::Play(path) => match load_replay(&path) {
+                    Ok(replay) => {
+                        self.start_replay(replay);
+                        self.mode = GameMode::Replay;
+                    }
+                    Err(err) => self.ui.set_replay_error(err),
+                },
+                ReplaySelectAction::Race(path) => match load_replay(&path) {
+                    Ok(replay) => {
+                        self.race(replay);
+                        self.mode = GameMode::Game;
+                    }
+                    Err(err) => self.ui.set_replay_error(err),
+                },
+                ReplaySelectAction::Cancel => self.mode = GameMode::Menu,
+                ReplaySelectAction::Noop => {}
+            },
+            GameMode::Replay => {
+                let Some(player) = self.replay.as_mut() else {
+                    self.mode = GameMode::ReplaySelect;
+                    return true;
+                };
+                if let ReplayAction::Exit = player.handle_key(key) {
+                    self.replay = None;
+                    self.ui.refresh_replays(replay_dir().as_deref());
+                    self.mode = GameMode::ReplaySelect;
+                } else {
+                    // Playback resumes from now, not from when it was paused
+                    self.last_frame = self.clock.now();
+                }
+            }
+            GameMode::Results => {
+                if let ResultsAction::Continue = handle_results_key(key) {
+                    self.mode = self.results.take().map_or(GameMode::Menu, |r| r.next);
+                }
+            }
+            GameMode::HighScores => {
+                if let HighScoresAction::Cancel = self.ui.handle_high_scores_key(key) {
+                    self.mode = GameMode::Menu;
+                }
+            }
+            GameMode::Game => match self.round.press(key) {
+                UserAction::Quit => self.end_round(GameMode::Menu),
+                UserAction::NewGame => self.end_round(GameMode::FileSelect),
+                UserAction::Screenshot(path) => {
+                    self.save_screenshot(&path, size.width, size.height)
+                }
+                _ => {}
+            },
+        }
+        true
+    }
+}
+
+/// Loads a replay to play or race, as long as its code did not change since.
+fn load_replay(path: &Path) -> Result<Replay, String> {
+    let replay = Replay::load(path).map_err(|err| err.to_string())?;
+    replay.check_source()?;
+    Ok(replay)
+}
+
+/// Waits up to `timeout` for input, or for good without one, then takes every queued event.
+fn drain_events(timeout: Option<Duration>) -> io::Result<Vec<Event>> {
+    let mut events = vec![];
+    if let Some(timeout) = timeout
+        && !crossterm::event::poll(timeout)?
+    {
+        return Ok(events);
+    }
+    // Blocks until an event arrives when there is no timeout
+    events.push(crossterm::event::read()?);
+    while crossterm::event::poll(Duration::ZERO)? {
+        events.push(crossterm::event::read()?);
+    }
+    Ok(events)
+}
+
+/// Feeds a key typed during a round through the input manager into the world.
+///
+/// Returns the resulting action, leaving quitting and starting a new game to the caller.
+pub(super) fn play_key(world: &mut World, input: &mut InputManager, key: KeyEvent) -> UserAction {
+    world.record_keystroke();
+    let action = input.handle_key(key);
+    match action {
+        UserAction::Motion((motion, count)) => world.apply_motion(motion, count),
+        UserAction::Rejected(violation) => world.reject_motion(violation),
+        UserAction::Hint => {
+            world.request_hint();
         }
-        Ok(())
+        _ => {}
     }
+    world.set_input_mode(input.mode());
+    action
 }
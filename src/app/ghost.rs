@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crate::{
+    app::replay::{Replay, ReplayPlayer},
+    domain::{Position, SharedSubscriber},
+};
+
+/// A recorded run raced against, played back in step with the live round.
+///
+/// The live round is started from the same seed and config as the recording,
+/// so the ghost sees the same buffer and spawns the player does, until their
+/// kills make the two worlds drift apart.
+pub struct Ghost {
+    player: ReplayPlayer,
+}
+
+impl Ghost {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            player: ReplayPlayer::new(replay),
+        }
+    }
+
+    pub fn replay(&self) -> &Replay {
+        self.player.replay()
+    }
+
+    pub fn cursor(&self) -> Position {
+        self.player.round().world().cursor().pos()
+    }
+
+    pub fn score(&self) -> usize {
+        self.player.round().score()
+    }
+
+    /// Plays the recording up to `elapsed` since the live round started.
+    pub fn follow(&mut self, elapsed: Duration) {
+        if elapsed > self.player.position() {
+            self.player.seek(elapsed);
+        }
+    }
+
+    /// Hands every event of the ghost's round to `subscriber`, from now on.
+    pub fn subscribe(&mut self, subscriber: SharedSubscriber) {
+        self.player.subscribe(subscriber);
+    }
+}
+
+#[cfg(test)]
+mod ghost_tests {
+    use super::*;
+    use crate::{
+        app::{input::char_key, simulation::Simulation},
+        domain::GameConfig,
+    };
+
+    #[test]
+    fn test_ghost_moves_as_recorded() {
+        let mut recorded = Simulation::new(GameConfig {
+            seed: Some(11),
+            ..GameConfig::default()
+        });
+        for c in "wjjwbe".chars() {
+            recorded.advance(Duration::from_millis(400));
+            recorded.press(char_key(c));
+        }
+        let replay = recorded.replay();
+
+        let start = Simulation::new(replay.config()).world().cursor().pos();
+        let mut ghost = Ghost::new(replay.clone());
+        ghost.follow(Duration::from_millis(300));
+        assert_eq!(ghost.cursor(), start);
+
+        ghost.follow(replay.duration());
+        assert_eq!(ghost.cursor(), recorded.world().cursor().pos());
+        assert_eq!(ghost.score(), replay.score);
+
+        // The ghost never goes back in time
+        ghost.follow(Duration::ZERO);
+        assert_eq!(ghost.cursor(), recorded.world().cursor().pos());
+    }
+}
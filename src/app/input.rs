@@ -1,16 +1,37 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::domain::{motions::Motion, types::BoundedQueue};
+use crate::domain::{
+    Drill, Edit, InputMode, Violation,
+    motions::{Motion, MotionKind},
+    types::BoundedQueue,
+};
 
 const EVENT_HISTORY_LEN: usize = 32;
 const MOTION_HISTORY_LEN: usize = 8;
 
+/// Builds the key event typing `c`, with Shift held for uppercase letters.
+pub fn char_key(c: char) -> KeyEvent {
+    let modifiers = if c.is_ascii_uppercase() {
+        KeyModifiers::SHIFT
+    } else {
+        KeyModifiers::NONE
+    };
+    KeyEvent::new(KeyCode::Char(c), modifiers)
+}
+
 /// Represents an action resulting from user input.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum UserAction {
     Motion((Motion, Option<usize>)),
+    /// An edit with an optional count, only produced when editing is enabled
+    Edit((Edit, Option<usize>)),
+    /// A motion rejected by the active drill
+    Rejected(Violation),
     Noop,
     Pending,
+    Hint,
+    /// Save the current frame to the given path
+    Screenshot(String),
     NewGame,
     Quit,
 }
@@ -25,6 +46,11 @@ impl UserAction {
     fn repeated_motion(motion: Motion, count: usize) -> Self {
         UserAction::Motion((motion, Some(count)))
     }
+
+    /// Creates a UserAction for an edit.
+    fn edit(edit: Edit, count: Option<usize>) -> Self {
+        UserAction::Edit((edit, count))
+    }
 }
 
 /// Represents the current state of input processing.
@@ -45,6 +71,10 @@ pub enum InputState {
         prefix: &'static str,
         count: Option<usize>,
     },
+    /// Typing a named command after `:`, run on Enter
+    Command(String),
+    /// Typing text into the buffer, until Esc
+    Insert,
 }
 
 /// Manages user input and translates it into actions.
@@ -52,6 +82,10 @@ pub struct InputManager {
     state: InputState,
     event_history: BoundedQueue<KeyEvent>,
     motion_history: BoundedQueue<Motion>,
+    /// Drill restricting the motions produced, if any
+    drill: Option<Drill>,
+    /// Whether edit keys (x, r, dd, i, a, o) are handled
+    editing: bool,
 }
 
 impl Default for InputManager {
@@ -60,6 +94,8 @@ impl Default for InputManager {
             state: InputState::default(),
             event_history: BoundedQueue::new(EVENT_HISTORY_LEN),
             motion_history: BoundedQueue::new(MOTION_HISTORY_LEN),
+            drill: None,
+            editing: false,
         }
     }
 }
@@ -71,22 +107,80 @@ impl InputManager {
         self.motion_history.clear();
     }
 
+    /// Restricts the motions produced to those allowed by `drill`.
+    pub fn set_drill(&mut self, drill: Option<Drill>) {
+        self.drill = drill;
+    }
+
+    /// Enables the edit keys, which are ignored otherwise.
+    pub fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+    }
+
+    /// Returns true while typing text into the buffer.
+    pub fn is_inserting(&self) -> bool {
+        matches!(self.state, InputState::Insert)
+    }
+
+    /// Returns what the next key typed does.
+    pub fn mode(&self) -> InputMode {
+        match self.state {
+            InputState::Idle => InputMode::Normal,
+            InputState::AwaitingCombo { prefix: ":", .. } | InputState::Command(_) => {
+                InputMode::Command
+            }
+            InputState::Counting(_)
+            | InputState::AwaitingTarget { .. }
+            | InputState::AwaitingCombo { .. } => InputMode::Pending,
+            InputState::Insert => InputMode::Insert,
+        }
+    }
+
     pub fn keys_iter(&self) -> impl Iterator<Item = &KeyEvent> {
         self.event_history.reverse_iter()
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> UserAction {
+        // `;` and `,` replay the last find/till, but are checked as their own kind
+        let repeat = match (&self.state, key.code) {
+            (InputState::Idle, KeyCode::Char(';')) => Some(MotionKind::RepeatFind),
+            (InputState::Idle, KeyCode::Char(',')) => Some(MotionKind::RepeatFindReverse),
+            _ => None,
+        };
+
         let action = match &self.state {
             InputState::Idle => self.handle_idle(key),
             InputState::Counting(count) => self.handle_counting(*count, key),
             InputState::AwaitingTarget { motion, count } => self.handle_target(motion, *count, key),
             InputState::AwaitingCombo { prefix, count } => self.handle_combo(prefix, *count, key),
+            InputState::Command(text) => self.handle_command(text.clone(), key),
+            InputState::Insert => self.handle_insert(key),
         };
+        let action = self.enforce(action, repeat);
 
         self.event_history.push(key);
         action
     }
 
+    /// Checks a motion against the active drill, recording it for `;` and `,` if allowed.
+    fn enforce(&mut self, action: UserAction, repeat: Option<MotionKind>) -> UserAction {
+        let UserAction::Motion((motion, count)) = action else {
+            return action;
+        };
+
+        let kind = repeat.unwrap_or(motion.kind());
+        if let Some(drill) = &self.drill
+            && let Err(violation) = drill.check(kind, count)
+        {
+            return UserAction::Rejected(violation);
+        }
+
+        if repeat.is_none() {
+            self.motion_history.push(motion);
+        }
+        action
+    }
+
     /// Maps simple key events to motions.
     fn map_key_to_motion(key: KeyEvent) -> Option<Motion> {
         match (key.code, key.modifiers) {
@@ -164,10 +258,37 @@ impl InputManager {
                 };
                 UserAction::Pending
             }
+            (KeyCode::F(1), KeyModifiers::NONE) => UserAction::Hint,
+
+            (KeyCode::Char('x'), KeyModifiers::NONE) if self.editing => {
+                UserAction::edit(Edit::DeleteChar, None)
+            }
+            (KeyCode::Char('r'), KeyModifiers::NONE) if self.editing => {
+                self.state = InputState::AwaitingTarget {
+                    motion: "r",
+                    count: None,
+                };
+                UserAction::Pending
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE) if self.editing => {
+                self.state = InputState::AwaitingCombo {
+                    prefix: "d",
+                    count: None,
+                };
+                UserAction::Pending
+            }
+            (KeyCode::Char(c @ ('i' | 'a' | 'o')), KeyModifiers::NONE) if self.editing => {
+                let edit = match c {
+                    'i' => Edit::Insert,
+                    'a' => Edit::Append,
+                    _ => Edit::OpenLineBelow,
+                };
+                self.state = InputState::Insert;
+                UserAction::edit(edit, None)
+            }
 
             _ => {
                 if let Some(motion) = Self::map_key_to_motion(key) {
-                    self.motion_history.push(motion);
                     UserAction::single_motion(motion)
                 } else {
                     UserAction::Noop
@@ -213,12 +334,22 @@ impl InputManager {
                 };
                 UserAction::Pending
             }
+            (KeyCode::Char('x'), KeyModifiers::NONE) if self.editing => {
+                self.state = InputState::Idle;
+                UserAction::edit(Edit::DeleteChar, Some(current))
+            }
+            (KeyCode::Char('d'), KeyModifiers::NONE) if self.editing => {
+                self.state = InputState::AwaitingCombo {
+                    prefix: "d",
+                    count: Some(current),
+                };
+                UserAction::Pending
+            }
 
             _ => {
                 self.state = InputState::Idle;
 
                 if let Some(motion) = Self::map_key_to_motion(key) {
-                    self.motion_history.push(motion);
                     UserAction::repeated_motion(motion, current)
                 } else {
                     UserAction::Noop
@@ -235,6 +366,10 @@ impl InputManager {
         key: KeyEvent,
     ) -> UserAction {
         match key.code {
+            KeyCode::Char(c) if motion == "r" => {
+                self.state = InputState::Idle;
+                UserAction::edit(Edit::ReplaceChar(c), None)
+            }
             KeyCode::Char(c) => {
                 let motion = match motion {
                     "f" => Motion::FindNextChar(c),
@@ -244,7 +379,6 @@ impl InputManager {
                     _ => unreachable!("Motion not recognized"),
                 };
 
-                self.motion_history.push(motion);
                 self.state = InputState::Idle;
 
                 if let Some(cnt) = count {
@@ -261,10 +395,14 @@ impl InputManager {
     fn handle_combo(
         &mut self,
         prefix: &'static str,
-        _count: Option<usize>,
+        count: Option<usize>,
         key: KeyEvent,
     ) -> UserAction {
         match (prefix, key.code, key.modifiers) {
+            ("d", KeyCode::Char('d'), KeyModifiers::NONE) => {
+                self.state = InputState::Idle;
+                UserAction::edit(Edit::DeleteLine, count)
+            }
             (":", KeyCode::Char('q'), KeyModifiers::NONE) => {
                 self.state = InputState::Idle;
                 UserAction::Quit
@@ -273,10 +411,218 @@ impl InputManager {
                 self.state = InputState::Idle;
                 UserAction::NewGame
             }
+            (":", KeyCode::Char(c), KeyModifiers::NONE) if c.is_ascii_lowercase() => {
+                self.state = InputState::Command(c.to_string());
+                UserAction::Pending
+            }
             _ => {
                 self.state = InputState::Idle;
                 UserAction::Noop
             }
         }
     }
+
+    /// Handle input from the Insert state.
+    fn handle_insert(&mut self, key: KeyEvent) -> UserAction {
+        let edit = match key.code {
+            KeyCode::Char(c) => Edit::InsertChar(c),
+            KeyCode::Tab => Edit::InsertChar('\t'),
+            KeyCode::Backspace => Edit::Backspace,
+            KeyCode::Enter => Edit::NewLine,
+            KeyCode::Esc => {
+                self.state = InputState::Idle;
+                Edit::StopInsert
+            }
+            _ => return UserAction::Noop,
+        };
+        UserAction::edit(edit, None)
+    }
+
+    /// Handle input from the Command state.
+    fn handle_command(&mut self, mut text: String, key: KeyEvent) -> UserAction {
+        match key.code {
+            KeyCode::Char(c) => {
+                text.push(c);
+                self.state = InputState::Command(text);
+                UserAction::Pending
+            }
+            KeyCode::Backspace => {
+                text.pop();
+                self.state = InputState::Command(text);
+                UserAction::Pending
+            }
+            KeyCode::Enter => {
+                self.state = InputState::Idle;
+                match text.split_once(' ') {
+                    None if text == "hint" => UserAction::Hint,
+                    Some(("screenshot", path)) if !path.trim().is_empty() => {
+                        UserAction::Screenshot(path.trim().to_string())
+                    }
+                    _ => UserAction::Noop,
+                }
+            }
+            _ => {
+                self.state = InputState::Idle;
+                UserAction::Noop
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::*;
+    use crate::domain::{DRILLS, enemies::SpawnRule};
+
+    fn press(input: &mut InputManager, keys: &str) -> UserAction {
+        let mut action = UserAction::Noop;
+        for c in keys.chars() {
+            action = input.handle_key(char_key(c));
+        }
+        action
+    }
+
+    fn enter(input: &mut InputManager) -> UserAction {
+        input.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_hint_key() {
+        let mut input = InputManager::default();
+        let action = input.handle_key(KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE));
+        assert!(matches!(action, UserAction::Hint));
+    }
+
+    #[test]
+    fn test_hint_command() {
+        let mut input = InputManager::default();
+        assert!(matches!(press(&mut input, ":hint"), UserAction::Pending));
+        assert!(matches!(enter(&mut input), UserAction::Hint));
+
+        // Back to normal input afterwards
+        assert!(matches!(
+            press(&mut input, "w"),
+            UserAction::Motion((Motion::WordStart, None))
+        ));
+    }
+
+    #[test]
+    fn test_screenshot_command() {
+        let mut input = InputManager::default();
+        press(&mut input, ":screenshot docs/Frame.svg");
+        assert!(matches!(
+            enter(&mut input),
+            UserAction::Screenshot(path) if path == "docs/Frame.svg"
+        ));
+
+        // A path is required
+        press(&mut input, ":screenshot ");
+        assert!(matches!(enter(&mut input), UserAction::Noop));
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let mut input = InputManager::default();
+        press(&mut input, ":hunt");
+        assert!(matches!(enter(&mut input), UserAction::Noop));
+    }
+
+    #[test]
+    fn test_drill_rejects_motions() {
+        let mut input = InputManager::default();
+        input.set_drill(Some(DRILLS[0]));
+
+        assert!(matches!(
+            press(&mut input, "j"),
+            UserAction::Rejected(Violation::NotAllowed(MotionKind::Down))
+        ));
+        assert!(matches!(
+            press(&mut input, "f("),
+            UserAction::Motion((Motion::FindNextChar('('), None))
+        ));
+        assert!(matches!(
+            press(&mut input, ";"),
+            UserAction::Motion((Motion::FindNextChar('('), None))
+        ));
+    }
+
+    #[test]
+    fn test_drill_rejected_find_is_not_repeated() {
+        let mut input = InputManager::default();
+        input.set_drill(Some(Drill {
+            name: "repeat only",
+            motions: &[MotionKind::RepeatFind],
+            require_count: false,
+            spawn_rule: SpawnRule::Anywhere,
+        }));
+
+        assert!(matches!(press(&mut input, "fa"), UserAction::Rejected(_)));
+        assert!(matches!(press(&mut input, ";"), UserAction::Noop));
+    }
+
+    #[test]
+    fn test_drill_requires_count() {
+        let mut input = InputManager::default();
+        input.set_drill(DRILLS.iter().find(|d| d.require_count).copied());
+
+        assert!(matches!(
+            press(&mut input, "w"),
+            UserAction::Rejected(Violation::CountRequired(MotionKind::WordStart))
+        ));
+        assert!(matches!(
+            press(&mut input, "2w"),
+            UserAction::Motion((Motion::WordStart, Some(2)))
+        ));
+    }
+
+    #[test]
+    fn test_edit_keys() {
+        let mut input = InputManager::default();
+        assert!(matches!(press(&mut input, "x"), UserAction::Noop));
+
+        input.set_editing(true);
+        assert!(matches!(
+            press(&mut input, "3x"),
+            UserAction::Edit((Edit::DeleteChar, Some(3)))
+        ));
+        assert!(matches!(
+            press(&mut input, "2dd"),
+            UserAction::Edit((Edit::DeleteLine, Some(2)))
+        ));
+        assert!(matches!(
+            press(&mut input, "rz"),
+            UserAction::Edit((Edit::ReplaceChar('z'), None))
+        ));
+    }
+
+    #[test]
+    fn test_insert_mode() {
+        let mut input = InputManager::default();
+        input.set_editing(true);
+
+        assert!(matches!(
+            press(&mut input, "a"),
+            UserAction::Edit((Edit::Append, None))
+        ));
+        assert!(matches!(
+            press(&mut input, "w"),
+            UserAction::Edit((Edit::InsertChar('w'), None))
+        ));
+        assert!(matches!(
+            input.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            UserAction::Edit((Edit::StopInsert, None))
+        ));
+        assert!(!input.is_inserting());
+        assert!(matches!(
+            press(&mut input, "w"),
+            UserAction::Motion((Motion::WordStart, None))
+        ));
+    }
+
+    #[test]
+    fn test_single_key_commands() {
+        let mut input = InputManager::default();
+        assert!(matches!(press(&mut input, ":q"), UserAction::Quit));
+        assert!(matches!(press(&mut input, ":n"), UserAction::NewGame));
+    }
 }
@@ -0,0 +1,142 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::input::{InputManager, UserAction, char_key},
+    domain::{Exercise, Grade, LESSONS, Lesson, LessonPhase, SharedClock},
+};
+
+const DEMO_STEP_INTERVAL: Duration = Duration::from_millis(600);
+
+/// Actions resulting from input during a lesson.
+pub enum LessonAction {
+    Noop,
+    /// The exercise was completed with the given grade
+    Completed(Grade),
+    /// Move on to the next lesson
+    Next,
+    Exit,
+}
+
+/// A running lesson: explanation, demonstration playback and the graded exercise.
+pub struct LessonSession {
+    index: usize,
+    phase: LessonPhase,
+    exercise: Exercise,
+    input: InputManager,
+    /// Demo keys not played back yet
+    demo_keys: VecDeque<char>,
+    clock: SharedClock,
+    last_demo_step: Instant,
+}
+
+impl LessonSession {
+    pub fn new(index: usize, clock: SharedClock) -> Self {
+        Self {
+            index,
+            phase: LessonPhase::Explanation,
+            exercise: Exercise::new(&LESSONS[index]),
+            input: InputManager::default(),
+            demo_keys: VecDeque::new(),
+            last_demo_step: clock.now(),
+            clock,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn lesson(&self) -> &'static Lesson {
+        &LESSONS[self.index]
+    }
+
+    pub fn phase(&self) -> LessonPhase {
+        self.phase
+    }
+
+    pub fn exercise(&self) -> &Exercise {
+        &self.exercise
+    }
+
+    /// Returns true once every demo key was played back.
+    pub fn demo_finished(&self) -> bool {
+        self.demo_keys.is_empty()
+    }
+
+    pub fn keys_iter(&self) -> impl Iterator<Item = &KeyEvent> {
+        self.input.keys_iter()
+    }
+
+    /// Plays back the next demo key once the step interval has passed.
+    pub fn tick(&mut self) {
+        let now = self.clock.now();
+        if self.phase != LessonPhase::Demo
+            || now.duration_since(self.last_demo_step) < DEMO_STEP_INTERVAL
+        {
+            return;
+        }
+
+        if let Some(c) = self.demo_keys.pop_front() {
+            self.press(char_key(c));
+            self.last_demo_step = now;
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> LessonAction {
+        match (self.phase, key.code) {
+            (_, KeyCode::Esc) => LessonAction::Exit,
+            (LessonPhase::Explanation, KeyCode::Enter) => {
+                self.restart(LessonPhase::Demo);
+                self.demo_keys = self.lesson().demo.chars().collect();
+                LessonAction::Noop
+            }
+            (LessonPhase::Demo, KeyCode::Enter) => {
+                self.restart(LessonPhase::Exercise);
+                LessonAction::Noop
+            }
+            (LessonPhase::Exercise, _) => {
+                self.exercise.record_keystroke();
+                if let UserAction::Quit = self.press(key) {
+                    return LessonAction::Exit;
+                }
+
+                match self.exercise.grade() {
+                    Some(grade) => {
+                        self.phase = LessonPhase::Result(grade);
+                        LessonAction::Completed(grade)
+                    }
+                    None => LessonAction::Noop,
+                }
+            }
+            (LessonPhase::Result(_), KeyCode::Enter) => LessonAction::Next,
+            (LessonPhase::Result(_), KeyCode::Char('r')) => {
+                self.restart(LessonPhase::Exercise);
+                LessonAction::Noop
+            }
+            _ => LessonAction::Noop,
+        }
+    }
+
+    /// Resets the exercise buffer and enters `phase`.
+    fn restart(&mut self, phase: LessonPhase) {
+        self.phase = phase;
+        self.exercise = Exercise::new(self.lesson());
+        self.input.reset();
+        self.demo_keys.clear();
+        self.last_demo_step = self.clock.now();
+    }
+
+    /// Feeds a key through the input manager, applying the resulting motion.
+    fn press(&mut self, key: KeyEvent) -> UserAction {
+        let action = self.input.handle_key(key);
+        if let UserAction::Motion((motion, count)) = action {
+            self.exercise.apply_motion(motion, count, self.clock.now());
+        }
+        action
+    }
+}
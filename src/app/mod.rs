@@ -1,4 +1,11 @@
 mod game;
+mod ghost;
 mod input;
+mod lesson;
+mod replay;
+mod simulation;
 
 pub use game::Game;
+pub use ghost::Ghost;
+pub use replay::{REPLAY_SPEEDS, Replay, ReplayAction, ReplayPlayer, replay_dir};
+pub use simulation::{Simulation, SimulationReport};
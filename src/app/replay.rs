@@ -0,0 +1,653 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    app::{input::char_key, simulation::Simulation},
+    domain::{
+        DRILLS, Difficulty, EventSubscriber, GameConfig, GameEvent, SharedSubscriber, World,
+        data_dir, enemies::SpawnRule,
+    },
+    tui::REPLAY_EXTENSION,
+};
+
+/// Playback speeds to pick from, in order.
+pub const REPLAY_SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+/// Time skipped by a single seek.
+const SEEK_STEP: Duration = Duration::from_secs(5);
+const KEYS_MARKER: &str = "=== keys";
+const FILE_MARKER: &str = "=== file";
+
+/// Returns the directory replays are saved in, under the data directory.
+pub fn replay_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("replays"))
+}
+
+/// A recorded round: everything needed to play it again exactly.
+///
+/// Replays are plain text files with `key: value` headers, followed by the keys
+/// after a marker line, one `<milliseconds> <key>` pair per line. Rounds played on
+/// a file end with its text after another marker, so they play without the file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    /// Name of the drill of the round, if any
+    pub drill: Option<String>,
+    /// File the buffer was loaded from, None for generated code
+    pub file_path: Option<String>,
+    /// Fingerprint of the code played, None for replays recorded before it was kept
+    pub source: Option<u64>,
+    /// Text of the file played, None for generated code or replays recorded before it was kept
+    pub file_contents: Option<String>,
+    pub difficulty: Difficulty,
+    /// Where enemies spawned, None for the drill's rule or anywhere
+    pub spawn_rule: Option<SpawnRule>,
+    /// Final score of the round
+    pub score: usize,
+    /// Keys pressed, with the time since the round started
+    pub keys: Vec<(Duration, KeyEvent)>,
+}
+
+impl Replay {
+    /// Builds the config the round was played with.
+    pub fn config(&self) -> GameConfig {
+        let drill = self
+            .drill
+            .as_ref()
+            .and_then(|name| DRILLS.iter().find(|d| d.name == name).copied());
+        let mut config =
+            GameConfig::for_round(drill, self.file_path.clone()).with_difficulty(self.difficulty);
+        if let Some(spawn_rule) = self.spawn_rule {
+            config = config.with_spawn_rule(spawn_rule);
+        }
+        GameConfig {
+            seed: Some(self.seed),
+            file_contents: self.file_contents.clone(),
+            ..config
+        }
+    }
+
+    /// Checks that the round would be played on the same code as recorded.
+    ///
+    /// Replays without the text of their file read it again, and it may have been
+    /// edited or removed since, in which case playing its keys again would give a
+    /// different round. Generated code may also change between versions of the game.
+    pub fn check_source(&self) -> Result<(), String> {
+        let Some(source) = self.source else {
+            return Ok(());
+        };
+        let world = World::new(self.config());
+        if world.buffer().fingerprint() == source {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} changed since the replay was recorded",
+                self.file_path.as_deref().unwrap_or("generated code")
+            ))
+        }
+    }
+
+    /// Returns the time of the last key.
+    pub fn duration(&self) -> Duration {
+        self.keys.last().map_or(Duration::ZERO, |(at, _)| *at)
+    }
+
+    /// Parses a replay file, failing on malformed headers or keys.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        // The file comes last and is kept as is, whatever it contains
+        let file_marker = format!("\n{}\n", FILE_MARKER);
+        let (contents, file_contents) = match contents.split_once(&file_marker) {
+            Some((contents, file)) => (contents, Some(file.to_string())),
+            None => (contents, None),
+        };
+        let (header, keys) = contents
+            .split_once(KEYS_MARKER)
+            .ok_or_else(|| format!("missing '{}' marker", KEYS_MARKER))?;
+
+        let mut replay = Replay {
+            seed: 0,
+            drill: None,
+            file_path: None,
+            source: None,
+            file_contents,
+            difficulty: Difficulty::default(),
+            spawn_rule: None,
+            score: 0,
+            keys: vec![],
+        };
+        let mut seed = None;
+        for line in header.lines().filter(|l| !l.trim().is_empty()) {
+            let (name, value) = line
+                .split_once(": ")
+                .ok_or_else(|| format!("malformed header '{}'", line))?;
+            match name {
+                "seed" => seed = value.parse().ok(),
+                "drill" => replay.drill = Some(value.to_string()),
+                "file" => replay.file_path = Some(value.to_string()),
+                "source" => {
+                    replay.source = Some(
+                        u64::from_str_radix(value, 16)
+                            .map_err(|_| format!("malformed source '{}'", value))?,
+                    )
+                }
+                "difficulty" => {
+                    replay.difficulty = Difficulty::from_name(value)
+                        .ok_or_else(|| format!("unknown difficulty '{}'", value))?
+                }
+                "spawn" => {
+                    replay.spawn_rule = Some(
+                        SpawnRule::from_name(value)
+                            .ok_or_else(|| format!("unknown spawn rule '{}'", value))?,
+                    )
+                }
+                "score" => replay.score = value.parse().unwrap_or_default(),
+                _ => return Err(format!("unknown header '{}'", name)),
+            }
+        }
+        replay.seed = seed.ok_or("missing seed")?;
+
+        for line in keys.lines().filter(|l| !l.trim().is_empty()) {
+            let key = line
+                .split_once(' ')
+                .and_then(|(at, key)| Some((Duration::from_millis(at.parse().ok()?), key)))
+                .and_then(|(at, key)| Some((at, decode_key(key)?)))
+                .ok_or_else(|| format!("malformed key '{}'", line))?;
+            replay.keys.push(key);
+        }
+        Ok(replay)
+    }
+
+    /// Loads a replay file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the replay to `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    /// Saves the replay in the replay directory, named after the current time.
+    ///
+    /// Returns the path written, or None if there is no data directory.
+    pub fn save_default(&self) -> io::Result<Option<PathBuf>> {
+        let Some(dir) = replay_dir() else {
+            return Ok(None);
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let path = dir.join(format!("{}.{}", now.as_millis(), REPLAY_EXTENSION));
+        self.save(&path)?;
+        Ok(Some(path))
+    }
+}
+
+/// Records a round as a replay, following its score through the round's events.
+pub struct ReplayRecorder {
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    /// Starts recording the round of `world`, played with `config`.
+    pub fn new(world: &World, config: &GameConfig) -> Self {
+        let drill_rule = config
+            .drill
+            .map_or_else(SpawnRule::default, |d| d.spawn_rule);
+        Self {
+            replay: Replay {
+                seed: world.seed(),
+                drill: config.drill.map(|d| d.name.to_string()),
+                file_path: config.file_path.clone(),
+                source: Some(world.buffer().fingerprint()),
+                file_contents: world.file_contents().map(str::to_string),
+                difficulty: config.difficulty,
+                spawn_rule: (config.enemy.spawn_rule != drill_rule)
+                    .then_some(config.enemy.spawn_rule),
+                score: 0,
+                keys: vec![],
+            },
+        }
+    }
+
+    /// Records a key pressed `at` since the round started.
+    pub fn record_key(&mut self, at: Duration, key: KeyEvent) {
+        self.replay.keys.push((at, key));
+    }
+
+    /// Returns the number of keys recorded.
+    pub fn keystrokes(&self) -> usize {
+        self.replay.keys.len()
+    }
+
+    /// Returns the recording so far.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+impl EventSubscriber for ReplayRecorder {
+    fn on_event(&mut self, event: &GameEvent, _now: Instant) {
+        match event {
+            GameEvent::RoundStarted { seed } => {
+                self.replay.seed = *seed;
+                self.replay.score = 0;
+                self.replay.keys.clear();
+            }
+            GameEvent::ScoreAwarded {
+                base, multiplier, ..
+            } => self.replay.score += base * multiplier,
+            GameEvent::HazardHit { penalty, .. } => self.replay.score -= penalty,
+            GameEvent::HintShown { cost, .. } => self.replay.score -= cost,
+            _ => {}
+        }
+    }
+}
+
+impl Display for Replay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "seed: {}", self.seed)?;
+        if let Some(drill) = &self.drill {
+            writeln!(f, "drill: {}", drill)?;
+        }
+        if let Some(path) = &self.file_path {
+            writeln!(f, "file: {}", path)?;
+        }
+        if let Some(source) = self.source {
+            writeln!(f, "source: {:016x}", source)?;
+        }
+        // Replays recorded before difficulties existed were played at normal
+        if self.difficulty != Difficulty::Normal {
+            writeln!(f, "difficulty: {}", self.difficulty)?;
+        }
+        if let Some(spawn_rule) = self.spawn_rule {
+            writeln!(f, "spawn: {}", spawn_rule)?;
+        }
+        writeln!(f, "score: {}", self.score)?;
+        writeln!(f, "{}", KEYS_MARKER)?;
+        for (at, key) in &self.keys {
+            writeln!(f, "{} {}", at.as_millis(), encode_key(key))?;
+        }
+        if let Some(file_contents) = &self.file_contents {
+            writeln!(f, "{}", FILE_MARKER)?;
+            write!(f, "{}", file_contents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a key in Vim notation, e.g. `w`, `<Esc>` or `<C-d>`.
+///
+/// Characters typed with their usual modifiers are written as is.
+fn encode_key(key: &KeyEvent) -> String {
+    if let KeyCode::Char(c) = key.code
+        && c != ' '
+        && c != '<'
+        && key.modifiers == char_key(c).modifiers
+    {
+        return c.to_string();
+    }
+
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char('<') => "lt".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Backspace => "BS".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => "Nop".to_string(),
+    };
+    let mut prefix = String::new();
+    for (modifier, letter) in [
+        (KeyModifiers::SHIFT, "S-"),
+        (KeyModifiers::CONTROL, "C-"),
+        (KeyModifiers::ALT, "A-"),
+    ] {
+        if key.modifiers.contains(modifier) {
+            prefix.push_str(letter);
+        }
+    }
+    format!("<{}{}>", prefix, name)
+}
+
+/// Reads a key written by `encode_key`.
+fn decode_key(text: &str) -> Option<KeyEvent> {
+    let mut chars = text.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(char_key(c));
+    }
+
+    let mut name = text.strip_prefix('<')?.strip_suffix('>')?;
+    let mut modifiers = KeyModifiers::NONE;
+    while let Some((letter, rest)) = name
+        .split_once('-')
+        .filter(|(l, r)| l.len() == 1 && !r.is_empty())
+    {
+        modifiers |= match letter {
+            "S" => KeyModifiers::SHIFT,
+            "C" => KeyModifiers::CONTROL,
+            "A" => KeyModifiers::ALT,
+            _ => return None,
+        };
+        name = rest;
+    }
+
+    let code = match name {
+        "Space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "BS" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Nop" => KeyCode::Null,
+        _ => match name.strip_prefix('F').and_then(|n| n.parse().ok()) {
+            Some(n) => KeyCode::F(n),
+            None => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        },
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+/// Reads a sequence of keys in Vim notation, such as `3w` or `fx<Esc>`.
+///
+/// A `<` not starting a known key name is read as itself.
+pub fn parse_keys(text: &str) -> Vec<KeyEvent> {
+    let mut keys = vec![];
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some(end) = rest.find('>')
+            && let Some(key) = decode_key(&rest[..=end])
+        {
+            keys.push(key);
+            rest = &rest[end + 1..];
+        } else {
+            keys.push(char_key(c));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    keys
+}
+
+/// Actions as a result of input during playback.
+pub enum ReplayAction {
+    Noop,
+    Exit,
+}
+
+/// Plays a replay back on a simulated round, at an adjustable speed.
+pub struct ReplayPlayer {
+    replay: Replay,
+    round: Simulation,
+    /// Index of the next key to press
+    next_key: usize,
+    /// Time of the recording reached so far
+    position: Duration,
+    /// Index in `REPLAY_SPEEDS`
+    speed: usize,
+    paused: bool,
+    /// Following the round played back, across seeks
+    subscribers: Vec<SharedSubscriber>,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            round: Simulation::new(replay.config()),
+            replay,
+            next_key: 0,
+            position: Duration::ZERO,
+            speed: 1,
+            paused: false,
+            subscribers: vec![],
+        }
+    }
+
+    /// Hands every event of the round played back to `subscriber`, from now on.
+    pub fn subscribe(&mut self, subscriber: SharedSubscriber) {
+        self.round.subscribe(subscriber.clone());
+        self.subscribers.push(subscriber);
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn round(&self) -> &Simulation {
+        &self.round
+    }
+
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    pub fn speed(&self) -> f32 {
+        REPLAY_SPEEDS[self.speed]
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns true once every key was played back.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.replay.duration()
+    }
+
+    /// Plays back what happened during `real` time, scaled by the speed.
+    pub fn update(&mut self, real: Duration) {
+        if !self.paused && !self.is_finished() {
+            self.play_until(self.position + real.mul_f32(self.speed()));
+        }
+    }
+
+    /// Jumps to `to`, replaying the round from the start when going back.
+    pub fn seek(&mut self, to: Duration) {
+        let to = to.min(self.replay.duration());
+        if to < self.position {
+            self.round = Simulation::new(self.replay.config());
+            for subscriber in &self.subscribers {
+                self.round.subscribe(subscriber.clone());
+            }
+            self.next_key = 0;
+            self.position = Duration::ZERO;
+        }
+        self.play_until(to);
+    }
+
+    /// Presses the recorded keys up to `to`, ticking the round in between.
+    fn play_until(&mut self, to: Duration) {
+        while let Some(&(at, key)) = self.replay.keys.get(self.next_key)
+            && at <= to
+        {
+            self.round.advance_to(at);
+            self.round.press(key);
+            self.next_key += 1;
+        }
+        self.round.advance_to(to);
+        self.position = to;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ReplayAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return ReplayAction::Exit,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = (self.speed + 1).min(REPLAY_SPEEDS.len() - 1)
+            }
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Char('l') | KeyCode::Right => self.seek(self.position + SEEK_STEP),
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.seek(self.position.saturating_sub(SEEK_STEP))
+            }
+            _ => {}
+        }
+        ReplayAction::Noop
+    }
+}
+
+#[cfg(test)]
+mod replay_tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::domain::RoundStats;
+
+    fn record(keys: &str) -> Replay {
+        let mut round = Simulation::new(GameConfig {
+            seed: Some(3),
+            ..GameConfig::for_round(Some(DRILLS[1]), None).with_difficulty(Difficulty::Hard)
+        });
+        for c in keys.chars() {
+            round.advance(Duration::from_millis(300));
+            round.press(char_key(c));
+        }
+        round.advance(Duration::from_secs(2));
+        round.replay()
+    }
+
+    #[test]
+    fn test_key_notation_round_trips() {
+        let keys = [
+            char_key('w'),
+            char_key('G'),
+            char_key(' '),
+            char_key('<'),
+            KeyEvent::new(KeyCode::Char(':'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
+        ];
+        for key in keys {
+            assert_eq!(decode_key(&encode_key(&key)), Some(key), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn test_replay_file_round_trips() {
+        let replay = record("wwbe");
+        assert_eq!(replay.drill.as_deref(), Some(DRILLS[1].name));
+        assert_eq!(replay.difficulty, Difficulty::Hard);
+        assert_eq!(Replay::parse(&replay.to_string()), Ok(replay));
+    }
+
+    #[test]
+    fn test_replay_keeps_the_picked_spawn_rule() {
+        let round = Simulation::new(GameConfig {
+            seed: Some(3),
+            ..GameConfig::for_round(None, None).with_spawn_rule(SpawnRule::OutsideComments)
+        });
+        let replay = Replay::parse(&round.replay().to_string()).unwrap();
+
+        assert_eq!(replay.spawn_rule, Some(SpawnRule::OutsideComments));
+        assert_eq!(replay.config().enemy.spawn_rule, SpawnRule::OutsideComments);
+        assert_eq!(record("w").spawn_rule, None);
+    }
+
+    #[test]
+    fn test_playback_matches_recording() {
+        let replay = record("wwjwbe3w");
+        let mut round = Simulation::new(replay.config());
+        for (at, key) in &replay.keys {
+            round.advance_to(*at);
+            round.press(*key);
+        }
+
+        let stats = Rc::new(RefCell::new(RoundStats::default()));
+        let mut player = ReplayPlayer::new(replay.clone());
+        player.subscribe(stats.clone());
+        player.seek(replay.duration());
+        assert_eq!(*stats.borrow(), *round.stats());
+        let played = player.round().world();
+        assert_eq!(played.cursor().pos(), round.world().cursor().pos());
+        assert_eq!(played.score(), round.world().score());
+
+        // Going back replays the round up to the earlier point
+        player.seek(Duration::ZERO);
+        assert_eq!(player.round().world().cursor().pos().col, 0);
+        player.update(replay.duration());
+        assert_eq!(
+            player.round().world().cursor().pos(),
+            round.world().cursor().pos()
+        );
+        // Subscribers follow the round replayed from the start
+        assert_eq!(*stats.borrow(), *round.stats());
+    }
+
+    #[test]
+    fn test_replay_plays_without_its_file() {
+        let path = std::env::temp_dir().join(format!("hjkl-embed-{}.rs", std::process::id()));
+        let text = "fn main() {\n=== keys\n    let x = 1;\n}\n";
+        fs::write(&path, text).unwrap();
+        let mut round = Simulation::new(GameConfig {
+            seed: Some(5),
+            ..GameConfig::for_round(None, Some(path.display().to_string()))
+        });
+        round.advance(Duration::from_millis(300));
+        round.press(char_key('j'));
+        round.press(char_key('w'));
+        let saved = round.replay().to_string();
+        fs::remove_file(&path).unwrap();
+
+        let replay = Replay::parse(&saved).unwrap();
+        assert_eq!(replay.file_contents.as_deref(), Some(text));
+        assert_eq!(replay.check_source(), Ok(()));
+        let mut played = Simulation::new(replay.config());
+        assert_eq!(
+            played.world().buffer().lines(),
+            round.world().buffer().lines()
+        );
+        for (at, key) in &replay.keys {
+            played.advance_to(*at);
+            played.press(*key);
+        }
+        assert_eq!(played.world().cursor().pos(), round.world().cursor().pos());
+    }
+
+    #[test]
+    fn test_replay_refuses_changed_source() {
+        let path = std::env::temp_dir().join(format!("hjkl-replay-{}.rs", std::process::id()));
+        fs::write(&path, "fn main() {\n    let x = 1;\n}\n").unwrap();
+        let mut round = Simulation::new(GameConfig {
+            seed: Some(5),
+            ..GameConfig::for_round(None, Some(path.display().to_string()))
+        });
+        round.press(char_key('w'));
+        let mut replay = Replay::parse(&round.replay().to_string()).unwrap();
+        assert_eq!(replay.check_source(), Ok(()));
+
+        // Replays recorded before files were kept in them read the file again
+        replay.file_contents = None;
+
+        fs::write(&path, "fn main() {\n    let y = 2;\n}\n").unwrap();
+        assert!(replay.check_source().is_err());
+        fs::remove_file(&path).unwrap();
+        // Missing files fall back to generated code, which is no match either
+        assert!(replay.check_source().is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::{
+        game::{INTERVAL, play_key},
+        input::{InputManager, UserAction},
+        replay::{Replay, ReplayRecorder, parse_keys},
+    },
+    domain::{
+        Clock, EventBus, GameConfig, GameEvent, ManualClock, RoundStats, Scenario, SharedClock,
+        SharedSubscriber, World,
+    },
+};
+
+/// A round played on a simulated clock, ticking at a fixed interval.
+///
+/// Keys go through the same input pipeline as the game, and time only passes
+/// when the simulation is stepped, so thousands of rounds run in no time. The
+/// game plays its rounds on it too, keeping it in step with real time, so that
+/// every round can be replayed exactly from its seed and keys.
+pub struct Simulation {
+    world: World,
+    input: InputManager,
+    clock: Rc<ManualClock>,
+    /// Simulated time between two ticks
+    tick: Duration,
+    /// Events pulled from the world so far, if collecting them
+    events: Vec<GameEvent>,
+    /// Whether events are kept for `take_events` and the report, besides being published
+    collect_events: bool,
+    /// Hands the events of the world to everyone following the round
+    bus: EventBus,
+    /// Records the keys and score of the round as a replay
+    recorder: Rc<RefCell<ReplayRecorder>>,
+    /// Statistics of the round, kept up to date from its events
+    stats: Rc<RefCell<RoundStats>>,
+    /// True once the player left the round
+    over: bool,
+}
+
+/// Outcome of a simulated round.
+pub struct SimulationReport {
+    pub score: usize,
+    pub stats: RoundStats,
+    /// Simulated time the round lasted
+    pub elapsed: Duration,
+    /// Every event of the round, in order, if collecting events
+    pub events: Vec<GameEvent>,
+    /// Final state of the world
+    pub world: World,
+}
+
+impl Simulation {
+    /// Starts a round with `config`, ticking as often as the game loop does.
+    pub fn new(config: GameConfig) -> Self {
+        let clock = Rc::new(ManualClock::new());
+        let mut input = InputManager::default();
+        input.set_drill(config.drill);
+        Self::with_world(
+            World::with_clock(config.clone(), clock.clone()),
+            &config,
+            input,
+            clock,
+        )
+    }
+
+    /// Starts a round set up as `scenario`, where only the player moves.
+    pub fn from_scenario(scenario: &Scenario) -> Self {
+        let clock = Rc::new(ManualClock::new());
+        let config = GameConfig::default().static_world();
+        let world = World::from_scenario(scenario, clock.clone());
+        Self::with_world(world, &config, InputManager::default(), clock)
+    }
+
+    /// Sets up the simulation of `world`, recording it as a replay and keeping its statistics.
+    fn with_world(
+        world: World,
+        config: &GameConfig,
+        input: InputManager,
+        clock: Rc<ManualClock>,
+    ) -> Self {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new(&world, config)));
+        let stats = Rc::new(RefCell::new(RoundStats::default()));
+        let mut bus = EventBus::default();
+        bus.subscribe(recorder.clone());
+        bus.subscribe(stats.clone());
+        Self {
+            world,
+            input,
+            clock,
+            tick: INTERVAL,
+            events: vec![],
+            collect_events: false,
+            bus,
+            recorder,
+            stats,
+            over: false,
+        }
+    }
+
+    /// Keeps every event of the round for `take_events` and the report.
+    ///
+    /// Off by default, as rounds played live would hold on to their events forever.
+    pub fn collecting_events(mut self) -> Self {
+        self.collect_events = true;
+        self
+    }
+
+    /// Hands every event of the round to `subscriber`, from the next step or key on.
+    pub fn subscribe(&mut self, subscriber: SharedSubscriber) {
+        self.bus.subscribe(subscriber);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn score(&self) -> usize {
+        self.world.score()
+    }
+
+    /// Returns the statistics of the round so far.
+    pub fn stats(&self) -> Ref<'_, RoundStats> {
+        self.stats.borrow()
+    }
+
+    /// Returns the clock of the round, for the UI to animate in step with it.
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    pub fn keys_iter(&self) -> impl Iterator<Item = &KeyEvent> {
+        self.input.keys_iter()
+    }
+
+    /// Returns the number of keys pressed so far.
+    pub fn keystrokes(&self) -> usize {
+        self.recorder.borrow().keystrokes()
+    }
+
+    /// Returns the simulated time since the round started.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Returns true once the player quit or asked for a new game.
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Advances the clock by one tick and ticks the world.
+    pub fn step(&mut self) {
+        self.clock.advance(self.tick);
+        self.world.tick();
+        self.pull_events();
+    }
+
+    /// Publishes the events of the world since the last pull, keeping them for
+    /// `take_events` if collecting events.
+    fn pull_events(&mut self) {
+        let events = self.world.pull_events();
+        self.bus.publish(&events, self.clock.now());
+        if self.collect_events {
+            self.events.extend(events);
+        }
+    }
+
+    /// Steps until `duration` of simulated time has passed.
+    pub fn advance(&mut self, duration: Duration) {
+        let until = self.clock.elapsed() + duration;
+        while self.clock.elapsed() < until {
+            self.step();
+        }
+    }
+
+    /// Steps as many whole ticks as fit until `elapsed` since the round started.
+    pub fn advance_to(&mut self, elapsed: Duration) {
+        while self.clock.elapsed() + self.tick <= elapsed {
+            self.step();
+        }
+    }
+
+    /// Presses a key between two ticks, as the player would, returning the resulting action.
+    ///
+    /// Keys pressed once the round is over are ignored.
+    pub fn press(&mut self, key: KeyEvent) -> UserAction {
+        if self.over {
+            return UserAction::Noop;
+        }
+        self.recorder
+            .borrow_mut()
+            .record_key(self.clock.elapsed(), key);
+        let action = play_key(&mut self.world, &mut self.input, key);
+        self.over = matches!(action, UserAction::Quit | UserAction::NewGame);
+        if self.over {
+            self.world.end_round();
+        }
+        self.pull_events();
+        action
+    }
+
+    /// Types `keys` in Vim notation, such as `3w` or `fx<Esc>`, one tick apart.
+    pub fn type_keys(&mut self, keys: &str) {
+        for key in parse_keys(keys) {
+            self.press(key);
+            self.step();
+        }
+    }
+
+    /// Takes the events pulled since the last call, empty unless collecting events.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Returns the replay of the round so far.
+    pub fn replay(&self) -> Replay {
+        self.recorder.borrow().replay().clone()
+    }
+
+    /// Ends the simulation, returning the outcome of the round.
+    pub fn finish(self) -> SimulationReport {
+        SimulationReport {
+            score: self.world.score(),
+            stats: self.stats.borrow().clone(),
+            elapsed: self.clock.elapsed(),
+            events: self.events,
+            world: self.world,
+        }
+    }
+}
+
+#[cfg(test)]
+mod simulation_tests {
+    use super::*;
+    use std::{fs, path::Path};
+
+    use crate::domain::Position;
+
+    fn config() -> GameConfig {
+        GameConfig {
+            seed: Some(7),
+            ..GameConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_keys_move_the_cursor() {
+        let mut sim = Simulation::new(config()).collecting_events();
+        sim.type_keys("jj");
+
+        assert_eq!(sim.world().cursor().pos().row, 2);
+        assert_eq!(sim.elapsed(), INTERVAL * 2);
+        let moves = sim
+            .take_events()
+            .into_iter()
+            .filter(|e| matches!(e, GameEvent::CursorMoved { .. }))
+            .count();
+        assert_eq!(moves, 2);
+    }
+
+    #[test]
+    fn test_events_are_only_kept_when_collecting() {
+        let mut sim = Simulation::new(config());
+        sim.advance(Duration::from_secs(10));
+        sim.type_keys("jj");
+
+        assert!(sim.take_events().is_empty());
+        // Subscribers still see every event
+        assert_eq!(sim.stats().motions, 2);
+    }
+
+    #[test]
+    fn test_same_seed_and_keys_give_same_report() {
+        let play = || {
+            let mut sim = Simulation::new(config()).collecting_events();
+            for _ in 0..20 {
+                sim.advance(Duration::from_millis(500));
+                sim.type_keys("wwjb");
+            }
+            let report = sim.finish();
+            let cursor: Position = report.world.cursor().pos();
+            (report.score, report.stats, report.events.len(), cursor)
+        };
+
+        assert_eq!(play(), play());
+    }
+
+    #[test]
+    fn test_scenario_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios");
+        let mut checked = 0;
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let scenario = Scenario::load(&path).unwrap();
+            let Some(expect) = scenario.expect else {
+                continue;
+            };
+
+            let mut sim = Simulation::from_scenario(&scenario);
+            assert_eq!(sim.world().cursor().pos(), scenario.cursor);
+            sim.type_keys(&scenario.keys);
+            assert_eq!(
+                sim.world().cursor().pos(),
+                expect,
+                "{} ({})",
+                scenario.title,
+                path.display()
+            );
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[derive(Default)]
+    struct Log(Vec<GameEvent>);
+
+    impl crate::domain::EventSubscriber for Log {
+        fn on_event(&mut self, event: &GameEvent, _now: std::time::Instant) {
+            self.0.push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_subscribers_follow_the_round() {
+        let log = Rc::new(RefCell::new(Log::default()));
+        let mut sim = Simulation::new(config());
+        sim.subscribe(log.clone());
+        sim.advance(Duration::from_secs(10));
+        sim.type_keys("2w:q<CR>");
+
+        let log = log.borrow();
+        assert!(matches!(log.0[0], GameEvent::RoundStarted { seed: 7 }));
+        assert!(matches!(log.0.last(), Some(GameEvent::RoundEnded { .. })));
+        assert!(
+            log.0
+                .iter()
+                .any(|e| matches!(e, GameEvent::EnemySpawned { .. }))
+        );
+        assert!(
+            log.0
+                .iter()
+                .any(|e| matches!(e, GameEvent::MotionApplied { count: Some(2), .. }))
+        );
+        assert!(log.0.iter().any(|e| matches!(
+            e,
+            GameEvent::ModeChanged {
+                mode: crate::domain::InputMode::Command
+            }
+        )));
+        assert_eq!(sim.replay().score, sim.score());
+    }
+
+    #[test]
+    fn test_quit_ends_simulation() {
+        let mut sim = Simulation::new(config());
+        sim.type_keys(":q");
+        assert!(sim.is_over());
+
+        // Keys after quitting are ignored
+        let cursor = sim.world().cursor().pos();
+        sim.type_keys("j");
+        assert_eq!(sim.world().cursor().pos(), cursor);
+    }
+}
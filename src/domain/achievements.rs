@@ -0,0 +1,163 @@
+use std::{fmt::Display, time::Instant};
+
+use crate::domain::{EventSubscriber, GameEvent, MAX_COMBO, ScoreReason};
+
+/// Kills needed in a round without getting hurt to be untouchable.
+const UNTOUCHABLE_KILLS: usize = 10;
+
+/// Feats unlocked by playing well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Achievement {
+    /// Destroyed a first enemy
+    FirstBlood,
+    /// Killed with a single motion covering a long distance
+    Sniper,
+    /// Chained kills up to the highest combo multiplier
+    ChainReaction,
+    /// Ended a round with many kills and no hazard hit
+    Untouchable,
+}
+
+impl Display for Achievement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Achievement::FirstBlood => "First blood",
+            Achievement::Sniper => "Sniper",
+            Achievement::ChainReaction => "Chain reaction",
+            Achievement::Untouchable => "Untouchable",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Achievements unlocked this session, watching the events of every round played.
+#[derive(Default)]
+pub struct Achievements {
+    unlocked: Vec<Achievement>,
+    /// Unlocked since the last call to `take_unlocked`
+    new: Vec<Achievement>,
+    /// Kills chained so far in the current combo
+    chain: usize,
+    kills: usize,
+    hurt: bool,
+}
+
+impl Achievements {
+    /// Returns every achievement unlocked so far, oldest first.
+    pub fn unlocked(&self) -> &[Achievement] {
+        &self.unlocked
+    }
+
+    /// Takes the achievements unlocked since the last call.
+    pub fn take_unlocked(&mut self) -> Vec<Achievement> {
+        std::mem::take(&mut self.new)
+    }
+
+    fn unlock(&mut self, achievement: Achievement) {
+        if !self.unlocked.contains(&achievement) {
+            self.unlocked.push(achievement);
+            self.new.push(achievement);
+        }
+    }
+}
+
+impl EventSubscriber for Achievements {
+    fn on_event(&mut self, event: &GameEvent, _now: Instant) {
+        match event {
+            GameEvent::RoundStarted { .. } => {
+                self.chain = 0;
+                self.kills = 0;
+                self.hurt = false;
+            }
+            GameEvent::EnemyDestroyed { .. } => {
+                self.kills += 1;
+                self.unlock(Achievement::FirstBlood);
+            }
+            GameEvent::ScoreAwarded { reason, .. } => match reason {
+                ScoreReason::Kill => self.chain = 1,
+                ScoreReason::Combo => {
+                    self.chain += 1;
+                    if self.chain >= MAX_COMBO {
+                        self.unlock(Achievement::ChainReaction);
+                    }
+                }
+                ScoreReason::LongShot => self.unlock(Achievement::Sniper),
+                _ => {}
+            },
+            GameEvent::HazardHit { .. } => self.hurt = true,
+            GameEvent::RoundEnded { .. } if self.kills >= UNTOUCHABLE_KILLS && !self.hurt => {
+                self.unlock(Achievement::Untouchable);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod achievements_tests {
+    use super::*;
+    use crate::domain::Position;
+
+    fn publish(achievements: &mut Achievements, events: &[GameEvent]) {
+        for event in events {
+            achievements.on_event(event, Instant::now());
+        }
+    }
+
+    fn kill(reason: ScoreReason) -> [GameEvent; 2] {
+        [
+            GameEvent::EnemyDestroyed {
+                position: Position::default(),
+            },
+            GameEvent::ScoreAwarded {
+                base: 10,
+                multiplier: 1,
+                reason,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_achievements_unlock_once() {
+        let mut achievements = Achievements::default();
+        publish(&mut achievements, &[GameEvent::RoundStarted { seed: 1 }]);
+        publish(&mut achievements, &kill(ScoreReason::Kill));
+        for _ in 1..MAX_COMBO {
+            publish(&mut achievements, &kill(ScoreReason::Combo));
+        }
+        assert_eq!(
+            achievements.take_unlocked(),
+            [Achievement::FirstBlood, Achievement::ChainReaction]
+        );
+
+        publish(&mut achievements, &kill(ScoreReason::Kill));
+        assert!(achievements.take_unlocked().is_empty());
+    }
+
+    #[test]
+    fn test_untouchable_needs_a_clean_round() {
+        let mut achievements = Achievements::default();
+        publish(&mut achievements, &[GameEvent::RoundStarted { seed: 1 }]);
+        for _ in 0..UNTOUCHABLE_KILLS {
+            publish(&mut achievements, &kill(ScoreReason::Kill));
+        }
+        publish(
+            &mut achievements,
+            &[
+                GameEvent::HazardHit {
+                    position: Position::default(),
+                    penalty: 20,
+                },
+                GameEvent::RoundEnded { score: 100 },
+            ],
+        );
+        assert!(!achievements.unlocked().contains(&Achievement::Untouchable));
+
+        publish(&mut achievements, &[GameEvent::RoundStarted { seed: 2 }]);
+        for _ in 0..UNTOUCHABLE_KILLS {
+            publish(&mut achievements, &kill(ScoreReason::Kill));
+        }
+        publish(&mut achievements, &[GameEvent::RoundEnded { score: 100 }]);
+        assert!(achievements.unlocked().contains(&Achievement::Untouchable));
+    }
+}
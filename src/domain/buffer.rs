@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range};
 
 use rand::Rng;
 
@@ -19,9 +19,34 @@ impl Buffer {
         self.0.get(row)
     }
 
-    /// Returns the length of the line at the specified row, or 0 if out of bounds
+    /// Returns all lines of the buffer
+    pub fn lines(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns the rows of the paragraph around `row`, a block of non-blank lines
+    ///
+    /// A blank row is a paragraph of its own.
+    pub fn paragraph_rows(&self, row: usize) -> Range<usize> {
+        let is_blank = |r: usize| self.get_line(r).is_none_or(|l| l.trim().is_empty());
+        if is_blank(row) {
+            return row..row + 1;
+        }
+
+        let mut start = row;
+        while start > 0 && !is_blank(start - 1) {
+            start -= 1;
+        }
+        let mut end = row + 1;
+        while end < self.rows() && !is_blank(end) {
+            end += 1;
+        }
+        start..end
+    }
+
+    /// Returns the length in characters of the line at the specified row, or 0 if out of bounds
     pub fn get_line_len(&self, row: usize) -> usize {
-        self.get_line(row).map_or(0, |line| line.len())
+        self.get_line(row).map_or(0, |line| line.chars().count())
     }
 
     /// Returns the character at the specified position, or None if out of bounds
@@ -44,6 +69,16 @@ impl Buffer {
         self.0.is_empty()
     }
 
+    /// Returns a hash of the text of the buffer with FNV-1a, which stays the same across builds
+    pub fn fingerprint(&self) -> u64 {
+        self.0
+            .iter()
+            .flat_map(|line| line.bytes().chain([b'\n']))
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
     /// Returns true if the line at the specified position is empty
     ///
     /// An empty line is considered one with zero length
@@ -66,7 +101,7 @@ impl Buffer {
     /// This shifts existing characters to the right
     pub fn insert_char(&mut self, pos: Position, c: char) {
         if let Some(line) = self.0.get_mut(pos.row) {
-            line.insert(pos.col, c);
+            line.insert(byte_offset(line, pos.col), c);
         }
     }
 
@@ -75,28 +110,53 @@ impl Buffer {
         self.0.push(line);
     }
 
+    /// Removes the line at the specified row, returning it
+    ///
+    /// This shifts existing lines up
+    pub fn remove_line(&mut self, row: usize) -> Option<String> {
+        (row < self.rows()).then(|| self.0.remove(row))
+    }
+
+    /// Splits the line at the specified position, moving the rest of it to a new line below
+    pub fn split_line(&mut self, pos: Position) {
+        if let Some(line) = self.0.get_mut(pos.row) {
+            let rest = line.split_off(byte_offset(line, pos.col));
+            self.0.insert(pos.row + 1, rest);
+        }
+    }
+
+    /// Appends the line below the specified row to it
+    pub fn join_lines(&mut self, row: usize) {
+        if row + 1 < self.rows() {
+            let next = self.0.remove(row + 1);
+            self.0[row].push_str(&next);
+        }
+    }
+
+    /// Removes the character at the specified position
+    ///
+    /// This shifts existing characters to the left
     pub fn delete_char(&mut self, pos: Position) {
         if let Some(line) = self.0.get_mut(pos.row)
-            && pos.col < line.len()
+            && let Some((offset, _)) = line.char_indices().nth(pos.col)
         {
-            line.remove(pos.col);
+            line.remove(offset);
         }
     }
 
     /// Return a random position on the buffer
-    pub fn random_position(&self, allow_space: bool) -> Option<Position> {
+    pub fn random_position(&self, allow_space: bool, rng: &mut impl Rng) -> Option<Position> {
         if self.is_empty() {
             return None;
         }
 
         loop {
-            let mut rng = rand::rng();
             let row = rng.random_range(0..self.rows());
             let line_len = self.get_line_len(row);
             if line_len == 0 {
                 continue;
             }
-            let col = rng.random_range(0..=line_len);
+            let col = rng.random_range(0..line_len);
             let pos = Position { row, col };
             if allow_space || !self.is_space(&pos) {
                 return Some(pos);
@@ -109,6 +169,7 @@ impl Buffer {
         start: Position,
         radius: usize,
         allow_space: bool,
+        rng: &mut impl Rng,
     ) -> Option<Position> {
         if self.is_empty() {
             return None;
@@ -120,14 +181,14 @@ impl Buffer {
         let end_col = start.col + radius;
 
         loop {
-            let mut rng = rand::rng();
             let row = rng.random_range(start_row..=end_row);
             let line_len = self.get_line_len(row);
             if line_len == 0 {
                 continue;
             }
 
-            let col = rng.random_range(start_col.min(line_len)..=end_col.min(line_len));
+            let last_col = line_len - 1;
+            let col = rng.random_range(start_col.min(last_col)..=end_col.min(last_col));
             let pos = Position { row, col };
 
             if allow_space || !self.is_space(&pos) {
@@ -137,6 +198,13 @@ impl Buffer {
     }
 }
 
+/// Returns the byte offset of the character at `col`, or the end of the line past it
+fn byte_offset(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(offset, _)| offset)
+}
+
 impl From<Vec<String>> for Buffer {
     fn from(lines: Vec<String>) -> Self {
         Buffer(lines)
@@ -227,4 +295,69 @@ mod buffer_tests {
         buffer.delete_char(Position { row: 1, col: 10 }); // Out of bounds, no change
         assert_eq!(buffer.get_line(1).unwrap(), "Worl");
     }
+
+    #[test]
+    fn test_paragraph_rows() {
+        let buffer = Buffer::from(vec![
+            String::from("fn a() {"),
+            String::from("}"),
+            String::from("  "),
+            String::from("fn b() {}"),
+        ]);
+
+        assert_eq!(buffer.paragraph_rows(1), 0..2);
+        assert_eq!(buffer.paragraph_rows(2), 2..3);
+        assert_eq!(buffer.paragraph_rows(3), 3..4);
+    }
+
+    #[test]
+    fn test_split_and_join_lines() {
+        let mut buffer = Buffer::from(vec![String::from("Hello World"), String::from("!")]);
+
+        buffer.split_line(Position { row: 0, col: 5 });
+        assert_eq!(buffer.lines(), ["Hello", " World", "!"]);
+
+        buffer.join_lines(1);
+        assert_eq!(buffer.lines(), ["Hello", " World!"]);
+
+        assert_eq!(buffer.remove_line(0).as_deref(), Some("Hello"));
+        assert_eq!(buffer.remove_line(1), None);
+        assert_eq!(buffer.lines(), [" World!"]);
+    }
+
+    #[test]
+    fn test_random_position_stays_on_the_line() {
+        let buffer = Buffer::from(vec![String::from("ab"), String::new(), String::from("c")]);
+        let mut rng = rand::rng();
+
+        for _ in 0..200 {
+            let pos = buffer.random_position(true, &mut rng).unwrap();
+            assert!(pos.col < buffer.get_line_len(pos.row));
+
+            let near = buffer
+                .random_position_from(Position { row: 1, col: 0 }, 3, true, &mut rng)
+                .unwrap();
+            assert!(near.col < buffer.get_line_len(near.row));
+        }
+    }
+
+    #[test]
+    fn test_edits_around_multibyte_chars() {
+        let mut buffer = Buffer::from(vec![String::from("caf\u{e9} ok")]);
+        assert_eq!(buffer.get_line_len(0), 7);
+
+        buffer.insert_char(Position { row: 0, col: 4 }, '!'); // After 'é'
+        assert_eq!(buffer.get_line(0).unwrap(), "caf\u{e9}! ok");
+        buffer.insert_char(Position { row: 0, col: 3 }, '\u{e8}'); // Before 'é'
+        assert_eq!(buffer.get_line(0).unwrap(), "caf\u{e8}\u{e9}! ok");
+
+        buffer.delete_char(Position { row: 0, col: 4 }); // Delete 'é'
+        assert_eq!(buffer.get_line(0).unwrap(), "caf\u{e8}! ok");
+        buffer.delete_char(Position { row: 0, col: 3 }); // Delete 'è'
+        assert_eq!(buffer.get_line(0).unwrap(), "caf! ok");
+
+        let mut buffer = Buffer::from(vec![String::from("\u{e9}t\u{e9}")]);
+        buffer.split_line(Position { row: 0, col: 1 });
+        assert_eq!(buffer.lines(), ["\u{e9}", "t\u{e9}"]);
+    }
 }
@@ -0,0 +1,291 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::domain::{Buffer, Cursor, Edit, SharedClock, motions::Motion};
+
+const START_MARKER: &str = "=== start";
+const GOAL_MARKER: &str = "=== goal";
+
+/// A buffer transformation challenge: edit the start text until it matches the goal.
+///
+/// Challenges are plain text files with an optional `title:` header, followed by
+/// the start and goal texts each introduced by a marker line. Trailing blank
+/// lines of both texts are ignored:
+///
+/// ```text
+/// title: Rename a variable
+/// === start
+/// let x = 1;
+/// === goal
+/// let count = 1;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Challenge {
+    pub title: String,
+    pub start: Vec<String>,
+    pub goal: Vec<String>,
+}
+
+/// Which part of a challenge file is being read.
+enum Section {
+    Header,
+    Start,
+    Goal,
+}
+
+impl Challenge {
+    /// Parses a challenge from the contents of a challenge file.
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        let mut title = None;
+        let mut start = None;
+        let mut goal = None;
+        let mut section = Section::Header;
+
+        for line in contents.lines() {
+            match line.trim_end() {
+                START_MARKER => {
+                    section = Section::Start;
+                    start = Some(vec![]);
+                    continue;
+                }
+                GOAL_MARKER => {
+                    section = Section::Goal;
+                    goal = Some(vec![]);
+                    continue;
+                }
+                _ => {}
+            }
+
+            match section {
+                Section::Header => {
+                    if let Some(value) = line.strip_prefix("title:") {
+                        title = Some(value.trim().to_string());
+                    }
+                }
+                Section::Start => start.get_or_insert_with(Vec::new).push(line.to_string()),
+                Section::Goal => goal.get_or_insert_with(Vec::new).push(line.to_string()),
+            }
+        }
+
+        let missing = |marker: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Missing '{}' section", marker),
+            )
+        };
+        let mut start: Vec<String> = start.ok_or_else(|| missing(START_MARKER))?;
+        let mut goal: Vec<String> = goal.ok_or_else(|| missing(GOAL_MARKER))?;
+
+        // Blank lines separating the sections are not part of the texts
+        for text in [&mut start, &mut goal] {
+            while text.last().is_some_and(|l| l.trim().is_empty()) {
+                text.pop();
+            }
+        }
+
+        Ok(Self {
+            title: title.unwrap_or_else(|| "Untitled".to_string()),
+            start: if start.is_empty() {
+                vec![String::new()]
+            } else {
+                start
+            },
+            goal,
+        })
+    }
+
+    /// Loads a challenge from the specified file path
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// A line of the diff between the buffer and the goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line already matching the goal
+    Same(String),
+    /// Line of the buffer that is not in the goal
+    Removed(String),
+    /// Line of the goal missing from the buffer
+    Added(String),
+}
+
+/// Computes a line diff turning `current` into `goal`, based on their longest common subsequence.
+pub fn diff_lines(current: &[String], goal: &[String]) -> Vec<DiffLine> {
+    let (n, m) = (current.len(), goal.len());
+
+    // lcs[i][j] is the LCS length of current[i..] and goal[j..]
+    let mut lcs = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if current[i] == goal[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && current[i] == goal[j] {
+            diff.push(DiffLine::Same(current[i].clone()));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(DiffLine::Removed(current[i].clone()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(goal[j].clone()));
+            j += 1;
+        }
+    }
+    diff
+}
+
+/// A challenge being played, scored on keystrokes and time.
+pub struct ChallengeRun {
+    challenge: Challenge,
+    buffer: Buffer,
+    cursor: Cursor,
+    keystrokes: usize,
+    clock: SharedClock,
+    started: Instant,
+    /// Time taken once solved
+    finished: Option<Duration>,
+}
+
+impl ChallengeRun {
+    /// Starts `challenge`, timed by `clock`.
+    pub fn new(challenge: Challenge, clock: SharedClock) -> Self {
+        Self {
+            buffer: challenge.start.clone().into(),
+            challenge,
+            cursor: Cursor::default(),
+            keystrokes: 0,
+            started: clock.now(),
+            clock,
+            finished: None,
+        }
+    }
+
+    pub fn challenge(&self) -> &Challenge {
+        &self.challenge
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    pub fn keystrokes(&self) -> usize {
+        self.keystrokes
+    }
+
+    /// Returns the time spent so far, or the final time once solved.
+    pub fn elapsed(&self) -> Duration {
+        self.finished
+            .unwrap_or_else(|| self.clock.now().duration_since(self.started))
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.finished.is_some()
+    }
+
+    /// Returns the remaining differences between the buffer and the goal.
+    pub fn diff(&self) -> Vec<DiffLine> {
+        diff_lines(self.buffer.lines(), &self.challenge.goal)
+    }
+
+    /// Counts a key typed by the player.
+    pub fn record_keystroke(&mut self) {
+        if !self.is_solved() {
+            self.keystrokes += 1;
+        }
+    }
+
+    pub fn apply_motion(&mut self, motion: Motion, count: Option<usize>) {
+        self.cursor
+            .apply_motion(&self.buffer, motion, count, self.clock.now());
+    }
+
+    /// Applies an edit at the cursor, checking whether the goal is reached
+    pub fn apply_edit(&mut self, edit: Edit, count: Option<usize>) {
+        if self.is_solved() {
+            return;
+        }
+
+        let pos = edit.apply(&mut self.buffer, self.cursor.pos(), count.unwrap_or(1));
+        self.cursor.move_to(pos);
+
+        if self.buffer.lines() == self.challenge.goal.as_slice() {
+            self.finished = Some(self.clock.now().duration_since(self.started));
+        }
+    }
+}
+
+#[cfg(test)]
+mod challenge_tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::domain::ManualClock;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        let contents = "title: Rename\n=== start\nlet x = 1;\n\n=== goal\nlet y = 1;\n";
+        let challenge = Challenge::parse(contents).unwrap();
+
+        assert_eq!(challenge.title, "Rename");
+        assert_eq!(challenge.start, lines(&["let x = 1;"]));
+        assert_eq!(challenge.goal, lines(&["let y = 1;"]));
+    }
+
+    #[test]
+    fn test_parse_challenge_missing_goal() {
+        assert!(Challenge::parse("=== start\nlet x = 1;\n").is_err());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let current = lines(&["a", "b", "c"]);
+        let goal = lines(&["a", "c", "d"]);
+
+        assert_eq!(
+            diff_lines(&current, &goal),
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Same("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_challenge_solved() {
+        let challenge = Challenge::parse("=== start\nlet x = 1;\n=== goal\nlet y = 1;").unwrap();
+        let clock = Rc::new(ManualClock::new());
+        let mut run = ChallengeRun::new(challenge, clock.clone());
+
+        run.apply_motion(Motion::FindNextChar('x'), None);
+        clock.advance(Duration::from_secs(2));
+        run.apply_edit(Edit::ReplaceChar('y'), None);
+        assert!(run.is_solved());
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(run.elapsed(), Duration::from_secs(2));
+        assert!(run.diff().iter().all(|l| matches!(l, DiffLine::Same(_))));
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Source of the current time for every timing decision of the game.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// A clock shared between the world and the UI.
+pub type SharedClock = Rc<dyn Clock>;
+
+/// The real monotonic clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock standing still until it is advanced, for tests and simulations.
+#[derive(Debug)]
+pub struct ManualClock {
+    origin: Instant,
+    elapsed: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            elapsed: Cell::new(Duration::ZERO),
+        }
+    }
+
+    /// Moves the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.set(self.elapsed.get() + by);
+    }
+
+    /// Returns the time advanced since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.get()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed.get()
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(clock.now() - start, Duration::from_millis(250));
+        assert_eq!(clock.elapsed(), Duration::from_millis(250));
+    }
+}
@@ -72,14 +72,17 @@ const IMPORTS: &[&str] = &[
 
 /// Loads a text buffer from the specified file path
 pub fn load_buffer_from_file<P: AsRef<Path>>(path: P) -> io::Result<Buffer> {
-    let contents = fs::read_to_string(path)?;
+    Ok(buffer_from_text(&fs::read_to_string(path)?))
+}
+
+/// Splits the text of a file into a buffer, one line per line.
+pub fn buffer_from_text(contents: &str) -> Buffer {
     let lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
-    Ok(Buffer::from(lines))
+    Buffer::from(lines)
 }
 
 /// Generates a random Rust code buffer, almost definitely won't compile :)
-pub fn generate_random_rust_code_buffer() -> Buffer {
-    let mut rng = rand::rng();
+pub fn generate_random_rust_code_buffer(rng: &mut impl Rng) -> Buffer {
     let mut buffer = Buffer::default();
 
     // Add some imports
@@ -87,7 +90,7 @@ pub fn generate_random_rust_code_buffer() -> Buffer {
     let mut used_imports = vec![];
 
     for _ in 0..import_count {
-        let import = IMPORTS.choose(&mut rng).unwrap();
+        let import = IMPORTS.choose(rng).unwrap();
         if !used_imports.contains(import) {
             buffer.push_line(import.to_string());
             used_imports.push(import);
@@ -98,7 +101,7 @@ pub fn generate_random_rust_code_buffer() -> Buffer {
     // Generate some structs
     let struct_count = rng.random_range(1..4);
     for _ in 0..struct_count {
-        let lines = random_struct_definition();
+        let lines = random_struct_definition(rng);
         for line in lines {
             buffer.push_line(line);
         }
@@ -115,33 +118,33 @@ pub fn generate_random_rust_code_buffer() -> Buffer {
         match block_type {
             1 => {
                 // Generate a random match block
-                let lines = random_match_block();
+                let lines = random_match_block(rng);
                 for line in lines {
                     buffer.push_line(format!("    {}", line));
                 }
             }
             2 => {
                 // Generate a random for loop
-                let lines = random_for_loop();
+                let lines = random_for_loop(rng);
                 for line in lines {
                     buffer.push_line(format!("    {}", line));
                 }
             }
             3 => {
                 // Generate a random while loop
-                let lines = random_while_loop();
+                let lines = random_while_loop(rng);
                 for line in lines {
                     buffer.push_line(format!("    {}", line));
                 }
             }
             4..=7 => {
                 // Generate a random function call line
-                let line = random_one_line_function_call();
+                let line = random_one_line_function_call(rng);
                 buffer.push_line(format!("    {}", line));
             }
             8..=10 => {
                 // Generate a random let statement
-                let line = random_let();
+                let line = random_let(rng);
                 buffer.push_line(format!("    {}", line));
             }
             _ => {}
@@ -159,17 +162,16 @@ pub fn generate_random_rust_code_buffer() -> Buffer {
     buffer
 }
 
-fn random_struct_definition() -> Vec<String> {
-    let mut rng = rand::rng();
+fn random_struct_definition(rng: &mut impl Rng) -> Vec<String> {
     let mut lines = vec![];
 
-    let struct_name = STRUCT_NAMES.choose(&mut rng).unwrap();
+    let struct_name = STRUCT_NAMES.choose(rng).unwrap();
     lines.push(format!("struct {} {{", struct_name));
 
     let field_count = rng.random_range(2..5);
     for _ in 0..field_count {
-        let field_name = VAR_NAMES.choose(&mut rng).unwrap();
-        let field_type = TYPE_NAMES.choose(&mut rng).unwrap();
+        let field_name = VAR_NAMES.choose(rng).unwrap();
+        let field_type = TYPE_NAMES.choose(rng).unwrap();
         lines.push(format!("    {}: {},", field_name, field_type));
     }
 
@@ -178,70 +180,66 @@ fn random_struct_definition() -> Vec<String> {
 }
 
 /// Generate a random sudo code block with pattern matching
-fn random_match_block() -> Vec<String> {
-    let mut rng = rand::rng();
+fn random_match_block(rng: &mut impl Rng) -> Vec<String> {
     let mut lines = vec![];
 
     let arms = rng.random_range(2..5);
     lines.push("match input {".to_string());
     for _ in 0..arms {
-        let pattern = VAR_NAMES.choose(&mut rng).unwrap();
-        let action = random_one_line_function_call();
+        let pattern = VAR_NAMES.choose(rng).unwrap();
+        let action = random_one_line_function_call(rng);
         lines.push(format!("    {} => {{ {} }},", pattern, action));
     }
 
-    let no_match_action = random_one_line_function_call();
+    let no_match_action = random_one_line_function_call(rng);
     lines.push(format!("    _ => {{ {} }},", no_match_action));
     lines.push("}".to_string());
     lines
 }
 
-fn random_let() -> String {
-    let mut rng = rand::rng();
-    let var_name = VAR_NAMES.choose(&mut rng).unwrap();
-    let fn_name = FN_NAMES.choose(&mut rng).unwrap();
+fn random_let(rng: &mut impl Rng) -> String {
+    let var_name = VAR_NAMES.choose(rng).unwrap();
+    let fn_name = FN_NAMES.choose(rng).unwrap();
     format!("let {} = {}::{};", var_name, var_name, fn_name)
 }
 
-fn random_while_loop() -> Vec<String> {
-    let mut rng = rand::rng();
+fn random_while_loop(rng: &mut impl Rng) -> Vec<String> {
     let mut lines = vec![];
 
-    let var = VAR_NAMES.choose(&mut rng).unwrap();
-    let var2 = VAR_NAMES.choose(&mut rng).unwrap();
+    let var = VAR_NAMES.choose(rng).unwrap();
+    let var2 = VAR_NAMES.choose(rng).unwrap();
     lines.push(format!("while {}.{}() {{", var, var2));
 
     let statement_count = rng.random_range(1..5);
     for _ in 0..statement_count {
         let is_let = rng.random_bool(0.5);
         let stmt = if is_let {
-            random_let()
+            random_let(rng)
         } else {
-            random_one_line_function_call()
+            random_one_line_function_call(rng)
         };
 
         lines.push(format!("    {}", stmt));
     }
 
     // Always add a function call at the end
-    lines.push(format!("    {}", random_one_line_function_call()));
+    lines.push(format!("    {}", random_one_line_function_call(rng)));
     lines.push("}".to_string());
 
     lines
 }
 
 /// Generate a random for loop block
-fn random_for_loop() -> Vec<String> {
-    let mut rng = rand::rng();
+fn random_for_loop(rng: &mut impl Rng) -> Vec<String> {
     let mut lines = vec![];
 
-    let var = VAR_NAMES.choose(&mut rng).unwrap();
+    let var = VAR_NAMES.choose(rng).unwrap();
     lines.push(format!("for {} in {}s {{", var, var));
 
     // Add 1-3 statements inside the loop
     let statement_count = rng.random_range(1..4);
     for _ in 0..statement_count {
-        let function_call = random_one_line_function_call();
+        let function_call = random_one_line_function_call(rng);
         lines.push(format!("    {}", function_call));
     }
 
@@ -251,13 +249,12 @@ fn random_for_loop() -> Vec<String> {
 }
 
 /// Generate a random one-line function call
-fn random_one_line_function_call() -> String {
-    let mut rng = rand::rng();
-    let fn_name = FN_NAMES.choose(&mut rng).unwrap();
+fn random_one_line_function_call(rng: &mut impl Rng) -> String {
+    let fn_name = FN_NAMES.choose(rng).unwrap();
     let arg_count = rng.random_range(0..4);
     let mut args = vec![];
     for _ in 0..arg_count {
-        let arg = VAR_NAMES.choose(&mut rng).unwrap();
+        let arg = VAR_NAMES.choose(rng).unwrap();
         args.push(arg.to_string());
     }
     format!("{}({});", fn_name, args.join(", "))
@@ -265,17 +262,20 @@ fn random_one_line_function_call() -> String {
 
 #[cfg(test)]
 mod generator_tests {
+    use rand::SeedableRng;
+
     use super::*;
+    use crate::domain::GameRng;
 
     #[test]
     fn test_generate_random_rust_code() {
-        let buffer = generate_random_rust_code_buffer();
+        let buffer = generate_random_rust_code_buffer(&mut rand::rng());
         println!("{}", buffer);
     }
 
     #[test]
     fn test_match_block_generation() {
-        let lines = random_match_block();
+        let lines = random_match_block(&mut rand::rng());
         for line in lines {
             println!("{}", line);
         }
@@ -283,7 +283,7 @@ mod generator_tests {
 
     #[test]
     fn test_while_loop_generation() {
-        let lines = random_while_loop();
+        let lines = random_while_loop(&mut rand::rng());
         for line in lines {
             println!("{}", line);
         }
@@ -291,7 +291,7 @@ mod generator_tests {
 
     #[test]
     fn test_for_loop_generation() {
-        let lines = random_for_loop();
+        let lines = random_for_loop(&mut rand::rng());
         for line in lines {
             println!("{}", line);
         }
@@ -299,21 +299,28 @@ mod generator_tests {
 
     #[test]
     fn test_let_statement_generation() {
-        let line = random_let();
+        let line = random_let(&mut rand::rng());
         println!("{}", line);
     }
 
     #[test]
     fn test_function_call_generation() {
-        let line = random_one_line_function_call();
+        let line = random_one_line_function_call(&mut rand::rng());
         println!("{}", line);
     }
 
     #[test]
     fn test_struct_definition_generation() {
-        let lines = random_struct_definition();
+        let lines = random_struct_definition(&mut rand::rng());
         for line in lines {
             println!("{}", line);
         }
     }
+
+    #[test]
+    fn test_same_seed_generates_same_buffer() {
+        let first = generate_random_rust_code_buffer(&mut GameRng::seed_from_u64(7));
+        let second = generate_random_rust_code_buffer(&mut GameRng::seed_from_u64(7));
+        assert_eq!(first.lines(), second.lines());
+    }
 }
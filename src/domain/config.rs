@@ -1,12 +1,165 @@
-use std::time::Duration;
+use std::{fmt::Display, time::Duration};
+
+use rand_chacha::ChaCha8Rng;
+
+use crate::domain::{
+    Drill,
+    enemies::{EnemyKind, SpawnRule},
+    is_scenario_path,
+};
+
+/// Interval of things that never happen.
+const NEVER: Duration = Duration::MAX;
+
+/// Random number generator behind every random decision of a round.
+///
+/// Seeded from `GameConfig::seed`, so a seed always plays out the same round. Unlike
+/// `StdRng`, its output is fixed across platforms and versions of `rand`, so shared
+/// seeds and replays play out the same for everyone.
+pub type GameRng = ChaCha8Rng;
+
+/// How fast enemies spawn and move.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the next difficulty, wrapping around after the hardest.
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Factor applied to the time between enemy spawns and moves.
+    fn pace(self) -> f64 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.6,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{}", text)
+    }
+}
 
 /// Top level configuration for the game domain layer
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct GameConfig {
     /// Enemy configuration
     pub enemy: EnemyConfig,
+    /// Projectile and fire configuration
+    pub hazard: HazardConfig,
+    /// Power-up configuration
+    pub powerup: PowerUpConfig,
     /// File to load at start, if not provided, a random buffer is generated
     pub file_path: Option<String>,
+    /// Contents of `file_path` to play instead of reading the file, such as the
+    /// copy kept in a replay
+    pub file_contents: Option<String>,
+    /// Points deducted from the score each time a hint is shown
+    pub hint_cost: usize,
+    /// Drill restricting the motions of the round, if any
+    pub drill: Option<Drill>,
+    /// Seed of the round's random number generator, a random one if not provided
+    pub seed: Option<u64>,
+    /// How fast enemies spawn and move
+    pub difficulty: Difficulty,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            enemy: EnemyConfig::default(),
+            hazard: HazardConfig::default(),
+            powerup: PowerUpConfig::default(),
+            file_path: None,
+            file_contents: None,
+            hint_cost: 10,
+            drill: None,
+            seed: None,
+            difficulty: Difficulty::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Config of a round of `drill`, if any, on the buffer loaded from `file_path`.
+    ///
+    /// Enemies spawn where the drill's motions shine, unless the file is a
+    /// scenario, whose world stands still.
+    pub fn for_round(drill: Option<Drill>, file_path: Option<String>) -> Self {
+        let scenario = file_path.as_deref().is_some_and(is_scenario_path);
+        let config = Self {
+            enemy: EnemyConfig {
+                spawn_rule: drill.map_or_else(SpawnRule::default, |d| d.spawn_rule),
+                ..EnemyConfig::default()
+            },
+            file_path,
+            drill,
+            ..Self::default()
+        };
+        if scenario {
+            config.static_world()
+        } else {
+            config
+        }
+    }
+
+    /// Paces enemy spawns and moves for `difficulty`.
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        let pace = |interval: Duration| {
+            // A static world stays static
+            if interval == NEVER {
+                interval
+            } else {
+                interval.mul_f64(difficulty.pace())
+            }
+        };
+        self.enemy.spawn_interval = pace(self.enemy.spawn_interval);
+        self.enemy.move_interval = pace(self.enemy.move_interval);
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Spawns enemies by `spawn_rule` instead of the drill's, or anywhere.
+    pub fn with_spawn_rule(mut self, spawn_rule: SpawnRule) -> Self {
+        self.enemy.spawn_rule = spawn_rule;
+        self
+    }
+
+    /// Turns off spawning, enemy moves, fire and power-ups, leaving only what is placed by hand.
+    pub fn static_world(mut self) -> Self {
+        self.enemy.spawn_interval = NEVER;
+        self.enemy.move_interval = NEVER;
+        self.hazard.fire_interval = NEVER;
+        self.powerup.spawn_interval = NEVER;
+        self
+    }
 }
 
 /// Configuration for enemy behavior
@@ -16,6 +169,12 @@ pub struct EnemyConfig {
     pub move_interval: Duration,
     pub move_radius: usize,
     pub spawn_interval: Duration,
+    /// Kinds of enemies to spawn, each with its relative weight
+    pub mix: Vec<(EnemyKind, u32)>,
+    /// Which cells of the code enemies may spawn on
+    pub spawn_rule: SpawnRule,
+    /// Enemies spawned in a wave before its boss appears
+    pub wave_size: usize,
 }
 
 impl Default for EnemyConfig {
@@ -25,6 +184,60 @@ impl Default for EnemyConfig {
             move_interval: Duration::from_millis(2500),
             move_radius: 3,
             spawn_interval: Duration::from_secs(2),
+            mix: EnemyKind::default_mix(),
+            spawn_rule: SpawnRule::default(),
+            wave_size: 15,
+        }
+    }
+}
+
+/// Configuration for projectiles and lines on fire
+#[derive(Clone, Debug)]
+pub struct HazardConfig {
+    /// Time for a projectile to fly one cell
+    pub projectile_step: Duration,
+    /// Time between two lines catching fire
+    pub fire_interval: Duration,
+    /// How long a line burns
+    pub fire_duration: Duration,
+    /// Points lost when stepping onto a hazard
+    pub penalty: usize,
+}
+
+impl Default for HazardConfig {
+    fn default() -> Self {
+        Self {
+            projectile_step: Duration::from_millis(150),
+            fire_interval: Duration::from_secs(12),
+            fire_duration: Duration::from_secs(3),
+            penalty: 20,
+        }
+    }
+}
+
+/// Configuration for power-ups
+#[derive(Clone, Debug)]
+pub struct PowerUpConfig {
+    /// Time between two power-ups appearing
+    pub spawn_interval: Duration,
+    /// Most power-ups lying on the buffer at once
+    pub max_items: usize,
+    pub freeze_duration: Duration,
+    pub magnet_duration: Duration,
+    /// Time for the magnet to pull enemies one cell closer
+    pub magnet_step: Duration,
+    pub double_score_duration: Duration,
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            spawn_interval: Duration::from_secs(15),
+            max_items: 2,
+            freeze_duration: Duration::from_secs(5),
+            magnet_duration: Duration::from_secs(5),
+            magnet_step: Duration::from_millis(400),
+            double_score_duration: Duration::from_secs(10),
         }
     }
 }
@@ -29,6 +29,12 @@ impl Cursor {
         self.position
     }
 
+    /// Moves the cursor to `position`, forgetting the column remembered by vertical motions.
+    pub fn move_to(&mut self, position: Position) {
+        self.position = position;
+        self.memory.target_col = None;
+    }
+
     /// Returns the last `n` recorded cursor positions with their timestamps.
     pub fn last_x_positions(&self, n: usize) -> Vec<(Instant, Position)> {
         let total = self.memory.position_history.len();
@@ -46,10 +52,16 @@ impl Cursor {
     }
 
     /// Applies the given motion to the cursor position within the provided buffer.
-    pub fn apply_motion(&mut self, buffer: &Buffer, motion: Motion, count: Option<usize>) {
-        self.memory
-            .position_history
-            .push((Instant::now(), self.position));
+    ///
+    /// The position left is recorded in the history at `now`.
+    pub fn apply_motion(
+        &mut self,
+        buffer: &Buffer,
+        motion: Motion,
+        count: Option<usize>,
+        now: Instant,
+    ) {
+        self.memory.position_history.push((now, self.position));
 
         let count = count.unwrap_or(1);
 
@@ -58,7 +70,7 @@ impl Cursor {
 
         if is_vertical {
             self.position.col = match self.memory.target_col {
-                Some(col) => col.min(buffer.get_line_len(self.position.row)),
+                Some(col) => col.min(buffer.get_line_len(self.position.row).saturating_sub(1)),
                 None => self.position.col,
             };
         } else {
@@ -68,6 +80,7 @@ impl Cursor {
 }
 
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod cursor_tests {
     use super::*;
 
@@ -88,4 +101,37 @@ mod cursor_tests {
         assert_eq!(last_positions[0].1, Position { row: 0, col: 3 });
         assert_eq!(last_positions[1].1, Position { row: 0, col: 4 });
     }
+
+    #[test]
+    fn test_vertical_motion_stays_on_line() {
+        let buffer: Buffer = vec![String::from("let value = 1;"), String::from("}")].into();
+        let mut cursor = Cursor::default();
+
+        let now = Instant::now();
+        cursor.apply_motion(&buffer, Motion::Right, Some(6), now);
+        cursor.apply_motion(&buffer, Motion::Down, None, now);
+        assert_eq!(cursor.pos(), Position { row: 1, col: 0 });
+    }
+
+    #[test]
+    fn test_vertical_motion_clamps_to_last_char() {
+        let buffer: Buffer = vec![
+            String::from("let value = 1;"),
+            String::from("  x += 1;"),
+            String::new(),
+        ]
+        .into();
+        let mut cursor = Cursor::default();
+
+        let now = Instant::now();
+        cursor.apply_motion(&buffer, Motion::Right, Some(12), now);
+        // The last char, not one past the end of the line
+        cursor.apply_motion(&buffer, Motion::Down, None, now);
+        assert_eq!(cursor.pos(), Position { row: 1, col: 8 });
+        cursor.apply_motion(&buffer, Motion::Down, None, now);
+        assert_eq!(cursor.pos(), Position { row: 2, col: 0 });
+        // The remembered column comes back on a long enough line
+        cursor.apply_motion(&buffer, Motion::Up, Some(2), now);
+        assert_eq!(cursor.pos(), Position { row: 0, col: 12 });
+    }
 }
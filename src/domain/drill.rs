@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use crate::domain::{
+    enemies::SpawnRule,
+    motions::{MotionKind, MotionSet},
+};
+
+/// A drill restricting which motions the player may use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Drill {
+    pub name: &'static str,
+    /// Motions allowed during the drill
+    pub motions: &'static [MotionKind],
+    /// Whether every motion must be typed with a count, `;` and `,` excepted
+    pub require_count: bool,
+    /// Where enemies spawn, to put them where the drill's motions shine
+    pub spawn_rule: SpawnRule,
+}
+
+/// Drills available from the menu.
+pub const DRILLS: &[Drill] = &[
+    Drill {
+        name: "No hjkl: find and till only",
+        motions: &[
+            MotionKind::FindNextChar,
+            MotionKind::FindPrevChar,
+            MotionKind::TillNextChar,
+            MotionKind::TillPrevChar,
+            MotionKind::RepeatFind,
+            MotionKind::RepeatFindReverse,
+        ],
+        require_count: false,
+        spawn_rule: SpawnRule::Punctuation,
+    },
+    Drill {
+        name: "Words only",
+        motions: &[
+            MotionKind::WordStart,
+            MotionKind::WordEnd,
+            MotionKind::WordBackward,
+        ],
+        require_count: false,
+        spawn_rule: SpawnRule::WordStarts,
+    },
+    Drill {
+        name: "No hjkl",
+        motions: &[
+            MotionKind::WordStart,
+            MotionKind::WordEnd,
+            MotionKind::WordBackward,
+            MotionKind::FindNextChar,
+            MotionKind::FindPrevChar,
+            MotionKind::TillNextChar,
+            MotionKind::TillPrevChar,
+            MotionKind::RepeatFind,
+            MotionKind::RepeatFindReverse,
+        ],
+        require_count: false,
+        spawn_rule: SpawnRule::OutsideComments,
+    },
+    Drill {
+        name: "Counts required",
+        motions: &MotionKind::ALL,
+        require_count: true,
+        spawn_rule: SpawnRule::Anywhere,
+    },
+];
+
+/// Why a motion was rejected by a drill.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// The motion is not part of the drill
+    NotAllowed(MotionKind),
+    /// The motion was typed without a count
+    CountRequired(MotionKind),
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::NotAllowed(kind) => write!(f, "'{}' is not allowed", kind.key()),
+            Violation::CountRequired(kind) => write!(f, "'{}' needs a count", kind.key()),
+        }
+    }
+}
+
+impl Drill {
+    /// Returns the allowed motions as a set.
+    pub fn motion_set(&self) -> MotionSet {
+        MotionSet::only(self.motions)
+    }
+
+    /// Checks a motion of `kind` typed with `count` against the drill.
+    pub fn check(&self, kind: MotionKind, count: Option<usize>) -> Result<(), Violation> {
+        let is_repeat = matches!(kind, MotionKind::RepeatFind | MotionKind::RepeatFindReverse);
+
+        if !self.motions.contains(&kind) {
+            Err(Violation::NotAllowed(kind))
+        } else if self.require_count && count.is_none() && !is_repeat {
+            Err(Violation::CountRequired(kind))
+        } else {
+            Ok(())
+        }
+    }
+}
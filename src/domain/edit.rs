@@ -0,0 +1,177 @@
+use crate::domain::{Buffer, Position};
+
+/// Represents the supported Vim edits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    DeleteChar,        // x
+    ReplaceChar(char), // r{char}
+    DeleteLine,        // dd
+
+    Insert,        // i - insert before the cursor
+    Append,        // a - insert after the cursor
+    OpenLineBelow, // o - insert on a new line below
+
+    // Typed in insert mode
+    InsertChar(char),
+    Backspace,
+    NewLine,
+    StopInsert, // <Esc>
+}
+
+impl Edit {
+    /// Returns true if this edit leaves the player in insert mode.
+    pub fn starts_insert(&self) -> bool {
+        matches!(self, Edit::Insert | Edit::Append | Edit::OpenLineBelow)
+    }
+
+    /// Applies the edit `count` times at `pos`, returning the new cursor position.
+    pub fn apply(&self, buffer: &mut Buffer, pos: Position, count: usize) -> Position {
+        let mut pos = pos;
+        for _ in 0..count.max(1) {
+            pos = self.apply_once(buffer, pos);
+        }
+        pos
+    }
+
+    fn apply_once(&self, buffer: &mut Buffer, pos: Position) -> Position {
+        let line_len = buffer.get_line_len(pos.row);
+
+        match self {
+            Edit::DeleteChar => {
+                buffer.delete_char(pos);
+                Position {
+                    row: pos.row,
+                    col: pos.col.min(line_len.saturating_sub(2)),
+                }
+            }
+            Edit::ReplaceChar(c) => {
+                if pos.col < line_len {
+                    buffer.delete_char(pos);
+                    buffer.insert_char(pos, *c);
+                }
+                pos
+            }
+            Edit::DeleteLine => {
+                buffer.remove_line(pos.row);
+                if buffer.is_empty() {
+                    buffer.push_line(String::new());
+                }
+                Position {
+                    row: pos.row.min(buffer.rows() - 1),
+                    col: 0,
+                }
+            }
+            Edit::Insert => pos,
+            Edit::Append => Position {
+                row: pos.row,
+                col: (pos.col + 1).min(line_len),
+            },
+            Edit::OpenLineBelow => {
+                buffer.insert_line(pos.row + 1, String::new());
+                Position {
+                    row: pos.row + 1,
+                    col: 0,
+                }
+            }
+            Edit::InsertChar(c) => {
+                buffer.insert_char(pos, *c);
+                Position {
+                    row: pos.row,
+                    col: pos.col + 1,
+                }
+            }
+            Edit::Backspace if pos.col > 0 => {
+                let col = pos.col - 1;
+                buffer.delete_char(Position { row: pos.row, col });
+                Position { row: pos.row, col }
+            }
+            Edit::Backspace if pos.row > 0 => {
+                // Join with the line above
+                let row = pos.row - 1;
+                let col = buffer.get_line_len(row);
+                buffer.join_lines(row);
+                Position { row, col }
+            }
+            Edit::Backspace => pos,
+            Edit::NewLine => {
+                buffer.split_line(pos);
+                Position {
+                    row: pos.row + 1,
+                    col: 0,
+                }
+            }
+            Edit::StopInsert => Position {
+                row: pos.row,
+                col: pos.col.saturating_sub(1),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod edit_tests {
+    use super::*;
+
+    fn buffer() -> Buffer {
+        vec![String::from("let x = 1;"), String::from("x += 1;")].into()
+    }
+
+    #[test]
+    fn test_delete_and_replace() {
+        let mut buffer = buffer();
+
+        let pos = Edit::DeleteChar.apply(&mut buffer, Position { row: 0, col: 8 }, 2);
+        assert_eq!(buffer.get_line(0).unwrap(), "let x = ");
+        assert_eq!(pos, Position { row: 0, col: 7 });
+
+        Edit::ReplaceChar('y').apply(&mut buffer, Position { row: 1, col: 0 }, 1);
+        assert_eq!(buffer.get_line(1).unwrap(), "y += 1;");
+    }
+
+    #[test]
+    fn test_insert_mode() {
+        let mut buffer = buffer();
+
+        let mut pos = Edit::Append.apply(&mut buffer, Position { row: 0, col: 4 }, 1);
+        for c in "yz".chars() {
+            pos = Edit::InsertChar(c).apply(&mut buffer, pos, 1);
+        }
+        pos = Edit::Backspace.apply(&mut buffer, pos, 1);
+        pos = Edit::NewLine.apply(&mut buffer, pos, 1);
+        assert_eq!(buffer.lines(), ["let xy", " = 1;", "x += 1;"]);
+        assert_eq!(pos, Position { row: 1, col: 0 });
+
+        pos = Edit::Backspace.apply(&mut buffer, pos, 1);
+        assert_eq!(buffer.lines(), ["let xy = 1;", "x += 1;"]);
+        assert_eq!(pos, Position { row: 0, col: 6 });
+    }
+
+    #[test]
+    fn test_line_edits() {
+        let mut buffer = buffer();
+
+        let pos = Edit::OpenLineBelow.apply(&mut buffer, Position { row: 0, col: 3 }, 1);
+        assert_eq!(pos, Position { row: 1, col: 0 });
+        assert_eq!(buffer.rows(), 3);
+
+        Edit::DeleteLine.apply(&mut buffer, Position { row: 0, col: 0 }, 5);
+        assert_eq!(buffer.lines(), [""]);
+    }
+
+    #[test]
+    fn test_edits_on_multibyte_chars() {
+        let mut buffer: Buffer = vec![String::from("let s = \"\";")].into();
+
+        let mut pos = Position { row: 0, col: 9 };
+        for c in "d\u{e9}j\u{e0}".chars() {
+            pos = Edit::InsertChar(c).apply(&mut buffer, pos, 1);
+        }
+        pos = Edit::Backspace.apply(&mut buffer, pos, 1);
+        assert_eq!(buffer.get_line(0).unwrap(), "let s = \"d\u{e9}j\";");
+        assert_eq!(pos, Position { row: 0, col: 12 });
+
+        Edit::ReplaceChar('e').apply(&mut buffer, Position { row: 0, col: 10 }, 1);
+        Edit::DeleteChar.apply(&mut buffer, Position { row: 0, col: 11 }, 1);
+        assert_eq!(buffer.get_line(0).unwrap(), "let s = \"de\";");
+    }
+}
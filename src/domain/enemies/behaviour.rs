@@ -0,0 +1,317 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque, hash_map::Entry},
+    time::Instant,
+};
+
+use rand::{Rng, RngCore, seq::IndexedRandom};
+
+use crate::domain::{
+    Buffer, Position,
+    motions::{Motion, MotionKind},
+};
+
+/// What an enemy can see of the world when deciding where to move.
+pub struct MoveContext<'a> {
+    pub buffer: &'a Buffer,
+    /// Position of the player's cursor
+    pub cursor: Position,
+    /// Cells the player's motions land on directly
+    pub landing_points: &'a HashSet<Position>,
+    /// Time of the tick
+    pub now: Instant,
+}
+
+/// Movement rules of an enemy kind.
+pub trait Behaviour {
+    /// Returns the position to move to from `from`, or None to stay in place.
+    ///
+    /// Random choices are drawn from `rng`.
+    fn next_position(
+        &mut self,
+        from: Position,
+        ctx: &MoveContext,
+        rng: &mut dyn RngCore,
+    ) -> Option<Position>;
+}
+
+/// Moves to a random non-whitespace cell within `radius`.
+pub struct Wander {
+    pub radius: usize,
+}
+
+impl Behaviour for Wander {
+    fn next_position(
+        &mut self,
+        from: Position,
+        ctx: &MoveContext,
+        mut rng: &mut dyn RngCore,
+    ) -> Option<Position> {
+        ctx.buffer
+            .random_position_from(from, self.radius, false, &mut rng)
+    }
+}
+
+/// Motions a Vim walker picks from.
+const WALK_MOTIONS: [MotionKind; 7] = [
+    MotionKind::WordStart,
+    MotionKind::WordEnd,
+    MotionKind::WordBackward,
+    MotionKind::FindNextChar,
+    MotionKind::FindPrevChar,
+    MotionKind::Down,
+    MotionKind::Up,
+];
+
+/// Random motions tried before a Vim walker gives up moving this turn
+const WALK_ATTEMPTS: usize = 8;
+
+/// Moves like a Vim user, with a random word, find or line motion applied up to
+/// `max_count` times.
+pub struct VimWalk {
+    pub max_count: usize,
+}
+
+impl Behaviour for VimWalk {
+    fn next_position(
+        &mut self,
+        from: Position,
+        ctx: &MoveContext,
+        rng: &mut dyn RngCore,
+    ) -> Option<Position> {
+        (0..WALK_ATTEMPTS).find_map(|_| {
+            let motion = random_walk_motion(ctx.buffer, from, rng)?;
+            let count = rng.random_range(1..=self.max_count.max(1));
+            let to = motion.apply(ctx.buffer, from, count);
+            let lands_on_text = ctx.buffer.get_char(&to).is_some() && !ctx.buffer.is_space(&to);
+            (to != from && lands_on_text).then_some(to)
+        })
+    }
+}
+
+/// Picks a random walk motion, targeting a character of the line for `f` and `F`.
+fn random_walk_motion(buffer: &Buffer, from: Position, rng: &mut dyn RngCore) -> Option<Motion> {
+    let kind = *WALK_MOTIONS.choose(rng)?;
+    if !kind.needs_target() {
+        return Motion::from_kind(kind);
+    }
+
+    let line: Vec<char> = buffer.get_line(from.row)?.chars().collect();
+    let candidates = match kind {
+        MotionKind::FindNextChar => line.get(from.col + 1..)?,
+        _ => line.get(..from.col)?,
+    };
+    let targets: Vec<char> = candidates
+        .iter()
+        .copied()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    Motion::with_target(kind, *targets.choose(rng)?)
+}
+
+/// Never moves.
+pub struct Stay;
+
+impl Behaviour for Stay {
+    fn next_position(
+        &mut self,
+        _from: Position,
+        _ctx: &MoveContext,
+        _rng: &mut dyn RngCore,
+    ) -> Option<Position> {
+        None
+    }
+}
+
+/// Walks up to `steps` cells toward the cursor along the shortest path through text,
+/// stopping next to it.
+pub struct Chase {
+    pub steps: usize,
+}
+
+impl Behaviour for Chase {
+    fn next_position(
+        &mut self,
+        from: Position,
+        ctx: &MoveContext,
+        _rng: &mut dyn RngCore,
+    ) -> Option<Position> {
+        let path = shortest_text_path(ctx.buffer, from, ctx.cursor)?;
+        let reachable = path.len().saturating_sub(1).min(self.steps);
+        reachable.checked_sub(1).map(|idx| path[idx])
+    }
+}
+
+/// Moves away from the cursor within `radius`, preferring cells that no common
+/// motion lands on directly.
+pub struct Flee {
+    pub radius: usize,
+}
+
+impl Behaviour for Flee {
+    fn next_position(
+        &mut self,
+        from: Position,
+        ctx: &MoveContext,
+        rng: &mut dyn RngCore,
+    ) -> Option<Position> {
+        let distance =
+            |p: &Position| p.row.abs_diff(ctx.cursor.row) + p.col.abs_diff(ctx.cursor.col);
+        let rank = |p: &Position| (!ctx.landing_points.contains(p), distance(p));
+
+        let rows = from.row.saturating_sub(self.radius)
+            ..(from.row + self.radius + 1).min(ctx.buffer.rows());
+        let candidates: Vec<Position> = rows
+            .flat_map(|row| {
+                let cols = from.col.saturating_sub(self.radius)
+                    ..(from.col + self.radius + 1).min(ctx.buffer.get_line_len(row));
+                cols.map(move |col| Position { row, col })
+            })
+            .filter(|p| !ctx.buffer.is_space(p) && distance(p) >= distance(&from))
+            .collect();
+
+        let best = candidates.iter().map(rank).max()?;
+        let best: Vec<Position> = candidates.into_iter().filter(|p| rank(p) == best).collect();
+        best.choose(rng).copied()
+    }
+}
+
+/// Finds the shortest path from `from` to `to` over non-whitespace cells.
+///
+/// Each step goes to the nearest non-whitespace cell up, down, left or right,
+/// hopping over whitespace and lines too short to reach the column. `to` itself
+/// is always walkable. The path excludes `from` and ends with `to`.
+pub fn shortest_text_path(buffer: &Buffer, from: Position, to: Position) -> Option<Vec<Position>> {
+    let mut previous = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(pos) = queue.pop_front() {
+        if pos == to {
+            let mut path = vec![pos];
+            let mut current = pos;
+            while let Some(&prev) = previous.get(&current).filter(|&&p| p != from) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for next in text_neighbours(buffer, pos, to) {
+            if let Entry::Vacant(entry) = previous.entry(next) {
+                entry.insert(pos);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the nearest walkable cell in each direction from `pos`.
+fn text_neighbours(buffer: &Buffer, pos: Position, goal: Position) -> Vec<Position> {
+    let walkable =
+        |p: &Position| p == &goal || (buffer.get_char(p).is_some() && !buffer.is_space(p));
+    let at = |row, col| Position { row, col };
+
+    [
+        (0..pos.col)
+            .rev()
+            .map(|col| at(pos.row, col))
+            .find(walkable),
+        (pos.col + 1..buffer.get_line_len(pos.row))
+            .map(|col| at(pos.row, col))
+            .find(walkable),
+        (0..pos.row)
+            .rev()
+            .map(|row| at(row, pos.col))
+            .find(walkable),
+        (pos.row + 1..buffer.rows())
+            .map(|row| at(row, pos.col))
+            .find(walkable),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod behaviour_tests {
+    use super::*;
+
+    fn buffer(lines: &[&str]) -> Buffer {
+        lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn test_shortest_text_path_avoids_whitespace() {
+        let buffer = buffer(&["ab  cd", "", "abcdef"]);
+        let from = Position { row: 0, col: 0 };
+        let to = Position { row: 0, col: 5 };
+
+        let path = shortest_text_path(&buffer, from, to).unwrap();
+        assert_eq!(path.last(), Some(&to));
+        assert!(path.iter().all(|p| !buffer.is_space(p)));
+        assert_eq!(path.len(), 3); // b, c, d
+    }
+
+    #[test]
+    fn test_chase_stops_next_to_cursor() {
+        let buffer = buffer(&["abcdef"]);
+        let landing_points = HashSet::new();
+        let ctx = MoveContext {
+            buffer: &buffer,
+            cursor: Position { row: 0, col: 5 },
+            landing_points: &landing_points,
+            now: Instant::now(),
+        };
+        let mut chase = Chase { steps: 10 };
+
+        let next = chase.next_position(Position { row: 0, col: 0 }, &ctx, &mut rand::rng());
+        assert_eq!(next, Some(Position { row: 0, col: 4 }));
+        assert_eq!(
+            chase.next_position(Position { row: 0, col: 4 }, &ctx, &mut rand::rng()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_vim_walk_lands_on_text() {
+        let buffer = buffer(&["fn main() {", "", "    let x = 1;", "}"]);
+        let landing_points = HashSet::new();
+        let ctx = MoveContext {
+            buffer: &buffer,
+            cursor: Position { row: 3, col: 0 },
+            landing_points: &landing_points,
+            now: Instant::now(),
+        };
+        let mut walk = VimWalk { max_count: 2 };
+
+        let mut pos = Position { row: 0, col: 0 };
+        for _ in 0..20 {
+            pos = walk
+                .next_position(pos, &ctx, &mut rand::rng())
+                .unwrap_or(pos);
+            assert!(buffer.get_char(&pos).is_some_and(|c| !c.is_whitespace()));
+        }
+    }
+
+    #[test]
+    fn test_flee_prefers_cells_off_landing_points() {
+        let buffer = buffer(&["abcdefgh"]);
+        let landing_points = HashSet::from([Position { row: 0, col: 7 }]);
+        let ctx = MoveContext {
+            buffer: &buffer,
+            cursor: Position { row: 0, col: 0 },
+            landing_points: &landing_points,
+            now: Instant::now(),
+        };
+        let mut flee = Flee { radius: 3 };
+
+        // col 7 is farthest but a landing point, col 6 is the best hiding spot
+        let next = flee.next_position(Position { row: 0, col: 4 }, &ctx, &mut rand::rng());
+        assert_eq!(next, Some(Position { row: 0, col: 6 }));
+    }
+}
@@ -1,15 +1,25 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
     time::{Duration, Instant},
 };
 
+use rand::{RngCore, seq::IndexedRandom};
+
 use crate::domain::{
-    Buffer, EnemyConfig, Position,
-    enemies::{pool::EnemyPool, spawner::EnemySpawner},
+    EnemyConfig, Heading, Position, Projectile,
+    enemies::{
+        behaviour::{Behaviour, MoveContext, shortest_text_path},
+        kind::EnemyKind,
+        pool::EnemyPool,
+        spawn_rule::{SpawnCells, random_identifier_region},
+        spawner::EnemySpawner,
+    },
+    motions::{Motion, MotionKind},
 };
 
 /// Unique identifier for an enemy.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct EnemyId(usize);
 
 impl From<usize> for EnemyId {
@@ -27,20 +37,31 @@ impl EnemyId {
 /// The collection of enemies in the game world,
 /// including active enemies, enemy pool, and spawner.
 pub struct Enemies {
-    /// Currently active enemies mapped by their IDs.
-    active: HashMap<EnemyId, Enemy>,
+    /// Currently active enemies mapped by their IDs, kept in ID order so rounds replay alike.
+    active: BTreeMap<EnemyId, Enemy>,
     /// Enemy pool for managing reusable enemy instances.
     enemy_pool: EnemyPool,
     /// Spawner for determining when to spawn new enemies.
     spawner: EnemySpawner,
+    /// Cells enemies may spawn on, by the configured spawn rule.
+    spawn_cells: SpawnCells,
+    /// Radius around a boss in which its minions spawn.
+    minion_radius: usize,
 }
 
 impl Enemies {
-    pub fn new(config: &EnemyConfig) -> Self {
+    pub fn new(config: &EnemyConfig, now: Instant) -> Self {
         Self {
-            active: HashMap::new(),
-            enemy_pool: EnemyPool::new(config.pool_size, config.move_interval, config.move_radius),
-            spawner: EnemySpawner::new(config.spawn_interval),
+            active: BTreeMap::new(),
+            enemy_pool: EnemyPool::new(config, now),
+            spawner: EnemySpawner::new(
+                config.spawn_interval,
+                config.mix.clone(),
+                config.wave_size,
+                now,
+            ),
+            spawn_cells: SpawnCells::new(config.spawn_rule),
+            minion_radius: config.move_radius,
         }
     }
 
@@ -54,9 +75,20 @@ impl Enemies {
         self.active.values().map(|e| e.pos()).collect()
     }
 
-    /// Gets a set of all enemy positions.
+    /// Gets a set of all cells occupied by enemies.
     pub fn position_set(&self) -> HashSet<Position> {
-        self.active.values().map(|e| e.pos()).collect()
+        self.active
+            .values()
+            .flat_map(|e| e.cells().iter().copied())
+            .collect()
+    }
+
+    /// Gets the kind of enemy at each occupied cell.
+    pub fn kind_map(&self) -> HashMap<Position, EnemyKind> {
+        self.active
+            .values()
+            .flat_map(|e| e.cells().iter().map(|cell| (*cell, e.kind())))
+            .collect()
     }
 
     /// Returns an iterator over all active enemies.
@@ -64,22 +96,130 @@ impl Enemies {
         self.active.values()
     }
 
+    /// Returns the number of the current wave, starting at 1.
+    pub fn wave(&self) -> usize {
+        self.spawner.wave()
+    }
+
+    /// Returns true if a boss is alive.
+    pub fn has_boss(&self) -> bool {
+        self.active.values().any(|e| e.kind() == EnemyKind::Boss)
+    }
+
     /// Advances the state of all enemies and spawns new ones as needed.
     ///
-    /// Only spawn if there are available enemies in the pool.
-    pub fn tick(&mut self, buffer: &Buffer) {
-        if self.spawner.should_spawn()
-            && let Some(mut enemy) = self.enemy_pool.take_enemy()
-        {
-            // spawn at a random position
-            let start_pos = buffer.random_position(false).unwrap_or_default();
-            enemy.move_to(start_pos);
-            self.active.insert(enemy.id(), enemy);
+    /// Only spawn if there are available enemies in the pool, on a cell allowed by the spawn rule.
+    /// A boss spawns at the end of each wave, and spawns minions around it whenever it acts.
+    /// Turrets shoot whenever they act; their projectiles are returned.
+    pub fn tick(&mut self, ctx: &MoveContext, rng: &mut impl RngCore) -> Vec<Projectile> {
+        if self.spawner.should_spawn(ctx.now) {
+            if self.spawner.wave_complete() && !self.has_boss() {
+                self.spawn_boss(ctx, rng);
+            } else if let Some(kind) = self.spawner.next_kind(rng)
+                && let Some(start_pos) = self.spawn_cells.random_position(ctx.buffer, rng)
+                && self.spawn(kind, vec![start_pos], ctx.now)
+            {
+                self.spawner.record_spawn();
+            }
+        }
+
+        let mut minion_spots = vec![];
+        let mut shots = vec![];
+        for enemy in self.active.values_mut() {
+            if !enemy.tick(ctx, rng) {
+                continue;
+            }
+            match enemy.kind() {
+                EnemyKind::Boss => minion_spots.extend(ctx.buffer.random_position_from(
+                    enemy.pos(),
+                    self.minion_radius,
+                    false,
+                    rng,
+                )),
+                EnemyKind::Turret => shots.push(Projectile {
+                    position: enemy.pos(),
+                    heading: Heading::toward(enemy.pos(), ctx.cursor)
+                        .or_else(|| Heading::ALL.choose(rng).copied())
+                        .unwrap_or(Heading::Right),
+                }),
+                _ => {}
+            }
+        }
+        for spot in minion_spots {
+            self.spawn(EnemyKind::Wanderer, vec![spot], ctx.now);
+        }
+        shots
+    }
+
+    /// Spawns the boss of the wave on a whole identifier, starting the next wave.
+    ///
+    /// Without an identifier wide enough for a boss, the wave ends without one.
+    fn spawn_boss(&mut self, ctx: &MoveContext, rng: &mut impl RngCore) {
+        let Some(region) = random_identifier_region(ctx.buffer, BOSS_MIN_WIDTH, rng) else {
+            self.spawner.next_wave();
+            return;
+        };
+        if self.spawn(EnemyKind::Boss, region, ctx.now) {
+            self.spawner.next_wave();
         }
+    }
+
+    /// Takes an enemy of `kind` from the pool and places it on `cells`.
+    ///
+    /// Returns false if the pool is empty.
+    pub fn spawn(&mut self, kind: EnemyKind, cells: Vec<Position>, now: Instant) -> bool {
+        match self.enemy_pool.take_enemy(kind, now) {
+            Some(mut enemy) => {
+                enemy.occupy(cells);
+                self.active.insert(enemy.id(), enemy);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Destroys every enemy within `rows` except bosses, returning their positions and kinds.
+    pub fn destroy_in_rows(&mut self, rows: Range<usize>) -> Vec<(Position, EnemyKind)> {
+        let ids: Vec<EnemyId> = self
+            .active
+            .values()
+            .filter(|e| e.kind() != EnemyKind::Boss && rows.contains(&e.pos().row))
+            .map(|e| e.id())
+            .collect();
+
+        let mut destroyed = vec![];
+        for id in ids {
+            if let Some(enemy) = self.active.get(&id) {
+                destroyed.push((enemy.pos(), enemy.kind()));
+            }
+            self.destroy(&id);
+        }
+        destroyed
+    }
 
+    /// Moves every enemy except bosses one step toward the cursor along the shortest path through text.
+    pub fn pull_toward_cursor(&mut self, ctx: &MoveContext) {
         for enemy in self.active.values_mut() {
-            enemy.tick(buffer);
+            if enemy.kind() == EnemyKind::Boss {
+                continue;
+            }
+            if let Some(path) = shortest_text_path(ctx.buffer, enemy.pos(), ctx.cursor)
+                && path.len() > 1
+            {
+                enemy.move_to(path[0]);
+            }
+        }
+    }
+
+    /// Hits an enemy by its ID on `cell` with `motion`, destroying it once it runs out of hit points.
+    ///
+    /// Returns the hit points left, or None if there is no such enemy.
+    pub fn hit(&mut self, id: &EnemyId, cell: Position, motion: &Motion) -> Option<usize> {
+        let remaining = self.active.get_mut(id)?.hit(cell, motion);
+        if remaining == 0 {
+            self.destroy(id);
         }
+        Some(remaining)
     }
 
     /// Destroys an enemy by its ID, returning it to the pool.
@@ -90,23 +230,41 @@ impl Enemies {
     }
 }
 
+/// Shortest identifier a boss spawns on
+const BOSS_MIN_WIDTH: usize = 4;
+
 /// An enemy in the game world.
 pub struct Enemy {
     id: EnemyId,
+    kind: EnemyKind,
+    hit_points: usize,
+    behaviour: Box<dyn Behaviour>,
     position: Position,
+    /// Cells occupied by the enemy, starting with its position
+    cells: Vec<Position>,
+    /// Cells and motions of the hits taken so far
+    hits: Vec<(Position, MotionKind)>,
     last_moved: Instant,
     move_interval: Duration,
-    move_radius: usize,
 }
 
 impl Enemy {
-    pub fn new(id: impl Into<EnemyId>, move_interval: Duration, move_radius: usize) -> Self {
+    pub fn new(
+        id: impl Into<EnemyId>,
+        kind: EnemyKind,
+        config: &EnemyConfig,
+        now: Instant,
+    ) -> Self {
         Self {
             id: id.into(),
+            kind,
+            hit_points: kind.hit_points(),
+            behaviour: kind.behaviour(config),
             position: Position::default(),
-            last_moved: Instant::now(),
-            move_interval,
-            move_radius,
+            cells: vec![Position::default()],
+            hits: vec![],
+            last_moved: now,
+            move_interval: config.move_interval,
         }
     }
 
@@ -114,35 +272,169 @@ impl Enemy {
         self.id
     }
 
+    pub fn kind(&self) -> EnemyKind {
+        self.kind
+    }
+
+    pub fn hit_points(&self) -> usize {
+        self.hit_points
+    }
+
     pub fn pos(&self) -> Position {
         self.position
     }
 
-    pub fn reset(&mut self) {
-        self.position = Position::default();
-        self.last_moved = Instant::now();
+    /// Returns the cells occupied by the enemy.
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+
+    /// Returns true if the enemy occupies `cell`.
+    pub fn occupies(&self, cell: Position) -> bool {
+        self.cells.contains(&cell)
+    }
+
+    /// Restores the enemy's hit points, its move interval starting over at `now`.
+    pub fn reset(&mut self, now: Instant) {
+        self.hit_points = self.kind.hit_points();
+        self.hits.clear();
+        self.move_to(Position::default());
+        self.last_moved = now;
+    }
+
+    /// Turns the enemy into a fresh enemy of `kind`.
+    pub fn set_kind(&mut self, kind: EnemyKind, config: &EnemyConfig, now: Instant) {
+        if self.kind != kind {
+            self.kind = kind;
+            self.behaviour = kind.behaviour(config);
+        }
+        self.reset(now);
+    }
+
+    /// Returns true if landing on `cell` with `motion` would damage the enemy.
+    ///
+    /// Each hit on an enemy must land on a new cell or come from a new kind of motion.
+    pub fn accepts_hit(&self, cell: Position, motion: &Motion) -> bool {
+        let kind = motion.kind();
+        self.kind.is_vulnerable_to(motion)
+            && (self.hits.iter().all(|(c, _)| *c != cell)
+                || self.hits.iter().all(|(_, k)| *k != kind))
+    }
+
+    /// Takes one hit point off the enemy, returning the hit points left.
+    pub fn hit(&mut self, cell: Position, motion: &Motion) -> usize {
+        self.hits.push((cell, motion.kind()));
+        self.hit_points = self.hit_points.saturating_sub(1);
+        self.hit_points
     }
 
     /// Advances the enemy's state, moving it if enough time has passed.
-    pub fn tick(&mut self, buffer: &Buffer) -> bool {
-        if self.last_moved.elapsed() >= self.move_interval {
-            self.move_random(buffer);
-            self.last_moved = Instant::now();
+    ///
+    /// Returns true if the enemy got to act this tick.
+    pub fn tick(&mut self, ctx: &MoveContext, rng: &mut dyn RngCore) -> bool {
+        if ctx.now.duration_since(self.last_moved) >= self.move_interval {
+            if let Some(position) = self.behaviour.next_position(self.position, ctx, rng) {
+                self.move_to(position);
+            }
+            self.last_moved = ctx.now;
             true
         } else {
             false
         }
     }
 
+    /// Moves the enemy to a single cell.
     pub fn move_to(&mut self, position: Position) {
-        self.position = position;
+        self.occupy(vec![position]);
+    }
+
+    /// Places the enemy on `cells`, the first one becoming its position.
+    pub fn occupy(&mut self, cells: Vec<Position>) {
+        self.position = cells.first().copied().unwrap_or_default();
+        self.cells = cells;
+    }
+}
+
+#[cfg(test)]
+mod enemy_tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_destroys_after_hit_points() {
+        let config = EnemyConfig::default();
+        let now = Instant::now();
+        let mut enemy = Enemy::new(0, EnemyKind::Wanderer, &config, now);
+        assert_eq!(enemy.hit_points(), EnemyKind::Wanderer.hit_points());
+
+        let cell = enemy.pos();
+        while enemy.hit(cell, &Motion::Left) > 0 {}
+        assert_eq!(enemy.hit_points(), 0);
+
+        enemy.set_kind(EnemyKind::Wanderer, &config, now);
+        assert_eq!(enemy.hit_points(), EnemyKind::Wanderer.hit_points());
+    }
+
+    #[test]
+    fn test_boss_hits_need_new_cell_or_motion() {
+        let config = EnemyConfig::default();
+        let mut boss = Enemy::new(0, EnemyKind::Boss, &config, Instant::now());
+        let cells: Vec<Position> = (0..4).map(|col| Position { row: 0, col }).collect();
+        boss.occupy(cells.clone());
+
+        assert!(boss.occupies(cells[3]));
+        assert!(boss.accepts_hit(cells[0], &Motion::WordStart));
+        boss.hit(cells[0], &Motion::WordStart);
+
+        // Same cell and same motion bounces off
+        assert!(!boss.accepts_hit(cells[0], &Motion::WordStart));
+        // A new cell or a new motion counts
+        assert!(boss.accepts_hit(cells[1], &Motion::WordStart));
+        assert!(boss.accepts_hit(cells[0], &Motion::FindNextChar('a')));
+    }
+
+    #[test]
+    fn test_enemy_acts_once_per_move_interval() {
+        let config = EnemyConfig::default();
+        let now = Instant::now();
+        let mut enemy = Enemy::new(0, EnemyKind::Turret, &config, now);
+        let buffer = crate::domain::Buffer::from(vec![String::from("abc")]);
+        let landing_points = HashSet::new();
+        let ctx = |now| MoveContext {
+            buffer: &buffer,
+            cursor: Position::default(),
+            landing_points: &landing_points,
+            now,
+        };
+
+        let rng = &mut rand::rng();
+        assert!(!enemy.tick(&ctx(now + config.move_interval / 2), rng));
+        assert!(enemy.tick(&ctx(now + config.move_interval), rng));
+        assert!(!enemy.tick(&ctx(now + config.move_interval), rng));
     }
 
-    pub fn move_random(&mut self, buffer: &Buffer) {
-        let new_position = buffer
-            .random_position_from(self.position, self.move_radius, false)
-            .unwrap_or(self.position);
-        self.position = new_position;
-        self.last_moved = Instant::now();
+    #[test]
+    fn test_waves_go_on_without_room_for_a_boss() {
+        let config = EnemyConfig {
+            mix: vec![(EnemyKind::Wanderer, 1)],
+            wave_size: 2,
+            ..EnemyConfig::default()
+        };
+        let start = Instant::now();
+        let mut enemies = Enemies::new(&config, start);
+        let buffer = crate::domain::Buffer::from(vec![String::from("a = b + c;")]);
+        let landing_points = HashSet::new();
+
+        let rng = &mut rand::rng();
+        for i in 1..=6 {
+            let ctx = MoveContext {
+                buffer: &buffer,
+                cursor: Position::default(),
+                landing_points: &landing_points,
+                now: start + config.spawn_interval * i,
+            };
+            enemies.tick(&ctx, rng);
+        }
+        assert!(!enemies.has_boss());
+        assert_eq!(enemies.wave(), 3);
     }
 }
@@ -0,0 +1,168 @@
+use crate::domain::{
+    EnemyConfig,
+    enemies::behaviour::{Behaviour, Chase, Flee, Stay, VimWalk, Wander},
+    motions::{Motion, MotionFamily},
+};
+
+/// Cells a chaser walks each time it moves
+const CHASER_STEPS: usize = 2;
+
+/// Highest count a walker types before its motions
+const WALKER_MAX_COUNT: usize = 2;
+
+/// The kinds of enemies, each with its own stats and movement rules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EnemyKind {
+    /// Wanders randomly around where it spawned
+    #[default]
+    Wanderer,
+    /// Hunts the cursor down along the shortest path through text
+    Chaser,
+    /// Runs away from the cursor toward cells that are hard to reach
+    Runner,
+    /// Wanders like a wanderer, but only dies to a motion of its family
+    Armored(MotionFamily),
+    /// Walks the code with real word, find and line motions
+    Walker,
+    /// Stands still and shoots projectiles along its row or column
+    Turret,
+    /// Holds a whole identifier at the end of a wave, spawning minions around it
+    Boss,
+}
+
+impl EnemyKind {
+    /// Every enemy kind.
+    pub const ALL: [EnemyKind; 9] = [
+        EnemyKind::Wanderer,
+        EnemyKind::Chaser,
+        EnemyKind::Runner,
+        EnemyKind::Armored(MotionFamily::Basic),
+        EnemyKind::Armored(MotionFamily::Word),
+        EnemyKind::Armored(MotionFamily::Find),
+        EnemyKind::Walker,
+        EnemyKind::Turret,
+        EnemyKind::Boss,
+    ];
+
+    /// Returns the default spawn mix, weighting every kind by its spawn weight.
+    pub fn default_mix() -> Vec<(EnemyKind, u32)> {
+        Self::ALL
+            .iter()
+            .map(|kind| (*kind, kind.spawn_weight()))
+            .collect()
+    }
+
+    /// Returns the name of this kind, as written in scenario files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyKind::Wanderer => "wanderer",
+            EnemyKind::Chaser => "chaser",
+            EnemyKind::Runner => "runner",
+            EnemyKind::Armored(MotionFamily::Basic) => "armored-basic",
+            EnemyKind::Armored(MotionFamily::Word) => "armored-word",
+            EnemyKind::Armored(MotionFamily::Find) => "armored-find",
+            EnemyKind::Walker => "walker",
+            EnemyKind::Turret => "turret",
+            EnemyKind::Boss => "boss",
+        }
+    }
+
+    /// Returns the kind called `name`, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Returns the glyph drawn for this kind.
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            EnemyKind::Wanderer => "👾",
+            EnemyKind::Chaser => "👹",
+            EnemyKind::Runner => "👻",
+            EnemyKind::Armored(MotionFamily::Basic) => "Ⓗ",
+            EnemyKind::Armored(MotionFamily::Word) => "Ⓦ",
+            EnemyKind::Armored(MotionFamily::Find) => "Ⓕ",
+            EnemyKind::Walker => "🤖",
+            EnemyKind::Turret => "🛸",
+            EnemyKind::Boss => "👑",
+        }
+    }
+
+    /// Returns the number of hits needed to destroy an enemy of this kind.
+    pub fn hit_points(&self) -> usize {
+        match self {
+            EnemyKind::Wanderer
+            | EnemyKind::Chaser
+            | EnemyKind::Runner
+            | EnemyKind::Armored(_)
+            | EnemyKind::Walker
+            | EnemyKind::Turret => 1,
+            EnemyKind::Boss => 5,
+        }
+    }
+
+    /// Returns true if landing on an enemy of this kind with `motion` damages it.
+    pub fn is_vulnerable_to(&self, motion: &Motion) -> bool {
+        match self {
+            EnemyKind::Armored(family) => motion.kind().family() == *family,
+            _ => true,
+        }
+    }
+
+    /// Returns the points awarded for destroying an enemy of this kind.
+    pub fn score(&self) -> usize {
+        match self {
+            EnemyKind::Wanderer => 10,
+            EnemyKind::Chaser | EnemyKind::Walker | EnemyKind::Turret => 20,
+            EnemyKind::Runner | EnemyKind::Armored(_) => 30,
+            EnemyKind::Boss => 100,
+        }
+    }
+
+    /// Returns how often this kind spawns relative to others in the default mix.
+    pub fn spawn_weight(&self) -> u32 {
+        match self {
+            EnemyKind::Wanderer => 10,
+            EnemyKind::Chaser => 3,
+            EnemyKind::Runner => 2,
+            EnemyKind::Armored(_) => 1,
+            EnemyKind::Walker => 3,
+            EnemyKind::Turret => 1,
+            // Bosses only come at the end of waves
+            EnemyKind::Boss => 0,
+        }
+    }
+
+    /// Creates the movement behaviour of this kind.
+    pub fn behaviour(&self, config: &EnemyConfig) -> Box<dyn Behaviour> {
+        match self {
+            EnemyKind::Wanderer | EnemyKind::Armored(_) => Box::new(Wander {
+                radius: config.move_radius,
+            }),
+            EnemyKind::Chaser => Box::new(Chase {
+                steps: CHASER_STEPS,
+            }),
+            EnemyKind::Runner => Box::new(Flee {
+                radius: config.move_radius,
+            }),
+            EnemyKind::Walker => Box::new(VimWalk {
+                max_count: WALKER_MAX_COUNT,
+            }),
+            EnemyKind::Turret | EnemyKind::Boss => Box::new(Stay),
+        }
+    }
+}
+
+#[cfg(test)]
+mod kind_tests {
+    use super::*;
+
+    #[test]
+    fn test_armored_is_vulnerable_to_its_family_only() {
+        let armored = EnemyKind::Armored(MotionFamily::Word);
+
+        assert!(armored.is_vulnerable_to(&Motion::WordStart));
+        assert!(armored.is_vulnerable_to(&Motion::WordEnd));
+        assert!(!armored.is_vulnerable_to(&Motion::FindNextChar('x')));
+        assert!(EnemyKind::Wanderer.is_vulnerable_to(&Motion::FindNextChar('x')));
+    }
+}
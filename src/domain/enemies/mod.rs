@@ -1,5 +1,11 @@
+mod behaviour;
 mod enemy;
+mod kind;
 mod pool;
+mod spawn_rule;
 mod spawner;
 
+pub use behaviour::*;
 pub use enemy::*;
+pub use kind::*;
+pub use spawn_rule::*;
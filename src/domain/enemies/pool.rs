@@ -1,29 +1,50 @@
-use std::collections::HashMap;
+use std::{collections::BTreeMap, time::Instant};
 
-use crate::domain::enemies::enemy::{Enemy, EnemyId};
+use crate::domain::{
+    EnemyConfig,
+    enemies::{
+        enemy::{Enemy, EnemyId},
+        kind::EnemyKind,
+    },
+};
 
-/// Pool managing reusable enemy instances.
+/// Pool managing reusable enemy instances of any kind.
 pub struct EnemyPool {
-    pool: HashMap<EnemyId, Enemy>,
+    /// Idle enemies in ID order, so the same enemies are taken on every run
+    pool: BTreeMap<EnemyId, Enemy>,
+    /// Config used to reconfigure enemies taken as another kind
+    config: EnemyConfig,
 }
 
 impl EnemyPool {
-    pub fn new(pool_size: usize, move_interval: std::time::Duration, move_radius: usize) -> Self {
-        let capacity = pool_size;
-        let mut enemies = HashMap::with_capacity(capacity);
+    pub fn new(config: &EnemyConfig, now: Instant) -> Self {
+        let capacity = config.pool_size;
+        let mut enemies = BTreeMap::new();
         for idx in 0..capacity {
-            let enemy = Enemy::new(idx, move_interval, move_radius);
+            let enemy = Enemy::new(idx, EnemyKind::default(), config, now);
             enemies.insert(idx.into(), enemy);
         }
-        Self { pool: enemies }
+        Self {
+            pool: enemies,
+            config: config.clone(),
+        }
     }
 }
 
 impl EnemyPool {
-    /// Takes an enemy from the pool, if available.
-    pub fn take_enemy(&mut self) -> Option<Enemy> {
-        let enemy_id = *self.pool.keys().next()?;
-        self.pool.remove(&enemy_id)
+    /// Takes an enemy of the given kind from the pool, if available.
+    ///
+    /// Prefers an instance already of that kind to avoid rebuilding its behaviour.
+    pub fn take_enemy(&mut self, kind: EnemyKind, now: Instant) -> Option<Enemy> {
+        let enemy_id = self
+            .pool
+            .values()
+            .find(|e| e.kind() == kind)
+            .or_else(|| self.pool.values().next())
+            .map(|e| e.id())?;
+        let mut enemy = self.pool.remove(&enemy_id)?;
+        enemy.set_kind(kind, &self.config, now);
+        Some(enemy)
     }
 
     /// Returns an enemy back to the pool.
@@ -0,0 +1,196 @@
+use std::fmt::Display;
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::domain::{
+    Buffer, Position,
+    motions::word_boundaries,
+    syntax::{TokenType, tokenize_line},
+};
+
+/// Where in the code enemies may spawn, based on the token under each cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpawnRule {
+    /// Any non-whitespace character
+    #[default]
+    Anywhere,
+    /// Identifiers, including type names
+    Identifiers,
+    /// Punctuation characters
+    Punctuation,
+    /// Inside string and char literals
+    Strings,
+    /// Any non-whitespace character outside comments
+    OutsideComments,
+    /// The first character of a word
+    WordStarts,
+}
+
+impl SpawnRule {
+    pub const ALL: [SpawnRule; 6] = [
+        SpawnRule::Anywhere,
+        SpawnRule::Identifiers,
+        SpawnRule::Punctuation,
+        SpawnRule::Strings,
+        SpawnRule::OutsideComments,
+        SpawnRule::WordStarts,
+    ];
+
+    /// Returns the next rule, or None after the last one.
+    pub fn next(self) -> Option<Self> {
+        let index = Self::ALL.iter().position(|r| *r == self)?;
+        Self::ALL.get(index + 1).copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.to_string() == name)
+    }
+
+    /// Returns every cell of the buffer allowed by the rule.
+    pub fn cells(&self, buffer: &Buffer) -> Vec<Position> {
+        let mut cells = vec![];
+
+        for (row, line) in buffer.lines().iter().enumerate() {
+            let mut col = 0;
+            for token in tokenize_line(line) {
+                for (offset, ch) in token.text.chars().enumerate() {
+                    if !ch.is_whitespace() && self.allows(token.token_type, &token.text, offset) {
+                        cells.push(Position { row, col });
+                    }
+                    col += 1;
+                }
+            }
+        }
+        cells
+    }
+
+    /// Returns true if the character at `offset` of a token is allowed.
+    fn allows(&self, token_type: TokenType, text: &str, offset: usize) -> bool {
+        match self {
+            SpawnRule::Anywhere => true,
+            SpawnRule::Identifiers => {
+                matches!(token_type, TokenType::Normal | TokenType::Type)
+                    && text.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            }
+            SpawnRule::Punctuation => token_type == TokenType::Punctuation,
+            SpawnRule::Strings => token_type == TokenType::String,
+            SpawnRule::OutsideComments => token_type != TokenType::Comment,
+            SpawnRule::WordStarts => {
+                offset == 0 && !matches!(token_type, TokenType::Comment | TokenType::String)
+            }
+        }
+    }
+}
+
+/// The cells a spawn rule allows, found once per buffer rather than on every spawn.
+#[derive(Debug, Default)]
+pub struct SpawnCells {
+    rule: SpawnRule,
+    /// Fingerprint of the buffer the cells were found in
+    fingerprint: Option<u64>,
+    cells: Vec<Position>,
+}
+
+impl SpawnCells {
+    pub fn new(rule: SpawnRule) -> Self {
+        Self {
+            rule,
+            ..Self::default()
+        }
+    }
+
+    /// Picks a random cell of the buffer allowed by the rule, tokenizing the
+    /// buffer again only if its text changed since the last pick.
+    pub fn random_position(&mut self, buffer: &Buffer, rng: &mut impl Rng) -> Option<Position> {
+        let fingerprint = buffer.fingerprint();
+        if self.fingerprint != Some(fingerprint) {
+            self.cells = self.rule.cells(buffer);
+            self.fingerprint = Some(fingerprint);
+        }
+        self.cells.choose(rng).copied()
+    }
+}
+
+/// Picks the cells of a random identifier at least `min_width` characters long.
+pub fn random_identifier_region(
+    buffer: &Buffer,
+    min_width: usize,
+    rng: &mut impl Rng,
+) -> Option<Vec<Position>> {
+    let regions: Vec<Vec<Position>> = SpawnRule::Identifiers
+        .cells(buffer)
+        .into_iter()
+        .filter_map(|pos| {
+            let (start, end) = word_boundaries(buffer.get_line(pos.row)?, pos.col)?;
+            (start == pos.col && end + 1 - start >= min_width).then(|| {
+                (start..=end)
+                    .map(|col| Position { row: pos.row, col })
+                    .collect()
+            })
+        })
+        .collect();
+    regions.choose(rng).cloned()
+}
+
+impl Display for SpawnRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SpawnRule::Anywhere => "anywhere",
+            SpawnRule::Identifiers => "identifiers",
+            SpawnRule::Punctuation => "punctuation",
+            SpawnRule::Strings => "strings",
+            SpawnRule::OutsideComments => "outside comments",
+            SpawnRule::WordStarts => "word starts",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod spawn_rule_tests {
+    use super::*;
+
+    fn cols(rule: SpawnRule, line: &str) -> Vec<usize> {
+        let buffer = Buffer::from(vec![line.to_string()]);
+        rule.cells(&buffer).iter().map(|p| p.col).collect()
+    }
+
+    #[test]
+    fn test_spawn_rule_cells() {
+        let line = r#"let s = "hi"; // note"#;
+
+        assert_eq!(cols(SpawnRule::Identifiers, line), vec![4]);
+        assert_eq!(cols(SpawnRule::Punctuation, line), vec![6, 12]);
+        assert_eq!(cols(SpawnRule::Strings, line), vec![8, 9, 10, 11]);
+        assert_eq!(cols(SpawnRule::WordStarts, line), vec![0, 4, 6, 12]);
+        assert!(
+            cols(SpawnRule::OutsideComments, line)
+                .iter()
+                .all(|&c| c < 14)
+        );
+    }
+
+    #[test]
+    fn test_spawn_cells_follow_the_buffer() {
+        let mut spawn_cells = SpawnCells::new(SpawnRule::Strings);
+        let mut rng = rand::rng();
+
+        let buffer = Buffer::from(vec![String::from(r#"let s = "a";"#)]);
+        for _ in 0..5 {
+            let pos = spawn_cells.random_position(&buffer, &mut rng).unwrap();
+            assert!((8..=10).contains(&pos.col));
+        }
+
+        let buffer = Buffer::from(vec![String::from("let s = 1;")]);
+        assert_eq!(spawn_cells.random_position(&buffer, &mut rng), None);
+    }
+
+    #[test]
+    fn test_random_identifier_region() {
+        let buffer = Buffer::from(vec![String::from("let counter = x;")]);
+        let region = random_identifier_region(&buffer, 4, &mut rand::rng()).unwrap();
+
+        assert_eq!(region.first(), Some(&Position { row: 0, col: 4 }));
+        assert_eq!(region.len(), "counter".len());
+    }
+}
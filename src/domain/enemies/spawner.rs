@@ -1,27 +1,80 @@
 use std::time::{Duration, Instant};
 
-/// Spawner that determines when to spawn new enemies.
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::domain::enemies::kind::EnemyKind;
+
+/// Spawner that determines when to spawn new enemies, and of which kind.
+///
+/// Enemies come in waves of `wave_size` spawns, each ended by a boss.
 pub struct EnemySpawner {
     last_spawned: Instant,
     interval: Duration,
+    /// Kinds to spawn with their weights
+    mix: Vec<(EnemyKind, u32)>,
+    wave_size: usize,
+    /// Current wave, starting at 1
+    wave: usize,
+    /// Enemies spawned in the current wave
+    spawned: usize,
 }
 
 impl EnemySpawner {
-    pub fn new(spawn_interval: Duration) -> Self {
+    pub fn new(
+        spawn_interval: Duration,
+        mix: Vec<(EnemyKind, u32)>,
+        wave_size: usize,
+        now: Instant,
+    ) -> Self {
         Self {
-            last_spawned: Instant::now(),
+            last_spawned: now,
             interval: spawn_interval,
+            mix,
+            wave_size,
+            wave: 1,
+            spawned: 0,
         }
     }
 }
 
 impl EnemySpawner {
-    pub fn should_spawn(&mut self) -> bool {
-        if self.last_spawned.elapsed() >= self.interval {
-            self.last_spawned = Instant::now();
+    /// Returns true if the spawn interval has passed at `now`, restarting it.
+    pub fn should_spawn(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.last_spawned) >= self.interval {
+            self.last_spawned = now;
             true
         } else {
             false
         }
     }
+
+    pub fn wave(&self) -> usize {
+        self.wave
+    }
+
+    /// Counts an enemy spawned in the current wave.
+    pub fn record_spawn(&mut self) {
+        self.spawned += 1;
+    }
+
+    /// Returns true once every enemy of the wave was spawned, and the boss is due.
+    pub fn wave_complete(&self) -> bool {
+        self.spawned >= self.wave_size
+    }
+
+    /// Starts the next wave.
+    pub fn next_wave(&mut self) {
+        self.wave += 1;
+        self.spawned = 0;
+    }
+
+    /// Picks the kind of the next enemy from the weighted mix.
+    ///
+    /// Returns None if the mix is empty or all its weights are zero.
+    pub fn next_kind(&self, rng: &mut impl Rng) -> Option<EnemyKind> {
+        self.mix
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .ok()
+            .map(|(kind, _)| *kind)
+    }
 }
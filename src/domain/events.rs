@@ -1,16 +1,163 @@
-use crate::domain::Position;
-use std::time::Instant;
+use crate::domain::{
+    Position, PowerUpKind, Route, ScoreReason, Violation, enemies::EnemyKind, motions::Motion,
+};
+use std::{cell::RefCell, fmt::Display, rc::Rc, time::Instant};
 
 /// Events that can occur in the game world.
 ///
 /// This is used to communicate between different systems in the game.
 #[derive(Debug, Clone)]
 pub enum GameEvent {
+    /// A round started from the given seed.
+    RoundStarted { seed: u64 },
+    /// The player left the round with the given final score.
+    RoundEnded { score: usize },
+    /// An enemy appeared at the given position.
+    EnemySpawned { position: Position, kind: EnemyKind },
+    /// An enemy moved from one position to another.
+    EnemyMoved { from: Position, to: Position },
     /// An enemy has been destroyed at the given position.
     EnemyDestroyed { position: Position },
+    /// Points were awarded, worth `base * multiplier`.
+    ScoreAwarded {
+        base: usize,
+        multiplier: usize,
+        reason: ScoreReason,
+    },
+    /// An enemy was hit but survived with the given hit points left.
+    EnemyHit {
+        position: Position,
+        remaining: usize,
+    },
+    /// The player landed on an armored enemy with the wrong motion and bounced off.
+    EnemyDeflected { position: Position, kind: EnemyKind },
+    /// The player stepped onto a hazard or got shot, losing `penalty` points.
+    HazardHit { position: Position, penalty: usize },
+    /// The player collected a power-up at the given position.
+    PowerUpCollected {
+        position: Position,
+        kind: PowerUpKind,
+    },
+    /// The effect of a timed power-up wore off.
+    PowerUpExpired { kind: PowerUpKind },
+    /// A motion was applied to the cursor, with its count if any.
+    MotionApplied {
+        motion: Motion,
+        count: Option<usize>,
+    },
     /// The player's cursor has moved to a new position at the given timestamp.
     CursorMoved {
         position: Position,
         timestamp: Instant,
     },
+    /// The input mode of the player changed.
+    ModeChanged { mode: InputMode },
+    /// A hint was requested, costing `cost` points, showing the cheapest route to the nearest enemy.
+    HintShown { route: Route, cost: usize },
+    /// A hint was requested but could not be shown.
+    HintRefused { reason: HintRefusal },
+    /// A motion was rejected by the drill.
+    MotionRejected { violation: Violation },
+}
+
+/// Why a requested hint could not be shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HintRefusal {
+    /// There is no enemy on the buffer
+    NoEnemies,
+    /// No enemy can be hit with the allowed motions
+    Unreachable,
+}
+
+impl Display for HintRefusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintRefusal::NoEnemies => write!(f, "no hint: there are no enemies"),
+            HintRefusal::Unreachable => write!(f, "no hint: no enemy can be reached"),
+        }
+    }
+}
+
+/// What the keys typed by the player currently do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputMode {
+    /// Keys are motions
+    #[default]
+    Normal,
+    /// A count, a find/till target or the rest of a combo is expected
+    Pending,
+    /// Typing a named command after `:`
+    Command,
+    /// Typing text into the buffer
+    Insert,
+}
+
+/// A consumer of game events, such as stats, achievements or effects.
+pub trait EventSubscriber {
+    /// Handles an event published at `now`.
+    fn on_event(&mut self, event: &GameEvent, now: Instant);
+}
+
+/// A subscriber shared between the bus and whoever reads its results.
+pub type SharedSubscriber = Rc<RefCell<dyn EventSubscriber>>;
+
+/// Hands every published event to its subscribers, in the order they subscribed.
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<SharedSubscriber>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self, subscriber: SharedSubscriber) {
+        self.subscribers.push(subscriber);
+    }
+
+    /// Publishes `events` at `now` to every subscriber.
+    pub fn publish(&self, events: &[GameEvent], now: Instant) {
+        for subscriber in &self.subscribers {
+            let mut subscriber = subscriber.borrow_mut();
+            for event in events {
+                subscriber.on_event(event, now);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter(usize);
+
+    impl EventSubscriber for Counter {
+        fn on_event(&mut self, event: &GameEvent, _now: Instant) {
+            if let GameEvent::EnemyDestroyed { .. } = event {
+                self.0 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_bus_reaches_every_subscriber() {
+        let first = Rc::new(RefCell::new(Counter::default()));
+        let second = Rc::new(RefCell::new(Counter::default()));
+        let mut bus = EventBus::default();
+        bus.subscribe(first.clone());
+        bus.subscribe(second.clone());
+
+        let destroyed = GameEvent::EnemyDestroyed {
+            position: Position::default(),
+        };
+        bus.publish(
+            &[
+                destroyed.clone(),
+                GameEvent::RoundEnded { score: 0 },
+                destroyed,
+            ],
+            Instant::now(),
+        );
+        assert_eq!(first.borrow().0, 2);
+        assert_eq!(second.borrow().0, 2);
+    }
 }
@@ -0,0 +1,230 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::domain::{Buffer, HazardConfig, Position};
+
+/// Direction a projectile flies in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Heading {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Heading {
+    pub const ALL: [Heading; 4] = [Heading::Up, Heading::Down, Heading::Left, Heading::Right];
+
+    /// Returns the heading from `from` straight toward `to`, if they share a row or column.
+    pub fn toward(from: Position, to: Position) -> Option<Heading> {
+        match (from.row.cmp(&to.row), from.col.cmp(&to.col)) {
+            (Ordering::Equal, Ordering::Less) => Some(Heading::Right),
+            (Ordering::Equal, Ordering::Greater) => Some(Heading::Left),
+            (Ordering::Less, Ordering::Equal) => Some(Heading::Down),
+            (Ordering::Greater, Ordering::Equal) => Some(Heading::Up),
+            _ => None,
+        }
+    }
+}
+
+/// A projectile flying along a row or column of the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Projectile {
+    pub position: Position,
+    pub heading: Heading,
+}
+
+impl Projectile {
+    /// Moves the projectile one cell, returning false once it leaves the buffer.
+    fn advance(&mut self, buffer: &Buffer) -> bool {
+        let Position { row, col } = self.position;
+        let next = match self.heading {
+            Heading::Up => row.checked_sub(1).map(|row| Position { row, col }),
+            Heading::Down => Some(Position { row: row + 1, col }),
+            Heading::Left => col.checked_sub(1).map(|col| Position { row, col }),
+            Heading::Right => Some(Position { row, col: col + 1 }),
+        };
+
+        // Vertical projectiles fly over lines too short to reach their column
+        match next {
+            Some(pos)
+                if pos.row < buffer.rows()
+                    && (self.is_vertical() || pos.col < buffer.get_line_len(pos.row)) =>
+            {
+                self.position = pos;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        matches!(self.heading, Heading::Up | Heading::Down)
+    }
+}
+
+/// Projectiles in flight and lines on fire.
+pub struct Hazards {
+    projectiles: Vec<Projectile>,
+    /// Burning rows mapped to when they stop burning
+    burning: HashMap<usize, Instant>,
+    last_step: Instant,
+    last_ignition: Instant,
+    config: HazardConfig,
+}
+
+impl Hazards {
+    pub fn new(config: &HazardConfig, now: Instant) -> Self {
+        Self {
+            projectiles: vec![],
+            burning: HashMap::new(),
+            last_step: now,
+            last_ignition: now,
+            config: config.clone(),
+        }
+    }
+
+    /// Returns the points lost when stepping onto a hazard.
+    pub fn penalty(&self) -> usize {
+        self.config.penalty
+    }
+
+    pub fn projectiles(&self) -> &[Projectile] {
+        &self.projectiles
+    }
+
+    /// Gets a set of all projectile positions.
+    pub fn projectile_set(&self) -> HashSet<Position> {
+        self.projectiles.iter().map(|p| p.position).collect()
+    }
+
+    /// Returns true if the row is on fire.
+    pub fn is_burning(&self, row: usize) -> bool {
+        self.burning.contains_key(&row)
+    }
+
+    /// Fires a projectile from the cell of its shooter, starting one cell ahead of it.
+    pub fn fire(&mut self, buffer: &Buffer, mut projectile: Projectile) {
+        if projectile.advance(buffer) {
+            self.projectiles.push(projectile);
+        }
+    }
+
+    /// Moves projectiles, puts out expired fires and sets a new line on fire now and then.
+    ///
+    /// The cursor's row is never set on fire, so that fire can only be stepped onto.
+    pub fn tick(&mut self, buffer: &Buffer, cursor: Position, now: Instant, rng: &mut impl Rng) {
+        if now.duration_since(self.last_step) >= self.config.projectile_step {
+            self.projectiles.retain_mut(|p| p.advance(buffer));
+            self.last_step = now;
+        }
+
+        self.burning.retain(|_, until| *until > now);
+
+        if now.duration_since(self.last_ignition) >= self.config.fire_interval {
+            let rows: Vec<usize> = (0..buffer.rows())
+                .filter(|&row| row != cursor.row && buffer.get_line_len(row) > 0)
+                .collect();
+            if let Some(row) = rows.choose(rng) {
+                self.burning.insert(*row, now + self.config.fire_duration);
+            }
+            self.last_ignition = now;
+        }
+    }
+
+    /// Removes the projectile at `pos`, returning true if there was one.
+    pub fn take_projectile_at(&mut self, pos: Position) -> bool {
+        let before = self.projectiles.len();
+        self.projectiles.retain(|p| p.position != pos);
+        self.projectiles.len() != before
+    }
+
+    /// Returns true if landing on `pos` steps onto a hazard, consuming any projectile there.
+    pub fn step_onto(&mut self, pos: Position) -> bool {
+        let hit_projectile = self.take_projectile_at(pos);
+        hit_projectile || self.is_burning(pos.row)
+    }
+}
+
+#[cfg(test)]
+mod hazards_tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_projectile_flies_until_leaving_buffer() {
+        let buffer = Buffer::from(vec![String::from("abc")]);
+        let mut projectile = Projectile {
+            position: Position { row: 0, col: 0 },
+            heading: Heading::Right,
+        };
+
+        assert!(projectile.advance(&buffer));
+        assert!(projectile.advance(&buffer));
+        assert_eq!(projectile.position, Position { row: 0, col: 2 });
+        assert!(!projectile.advance(&buffer));
+    }
+
+    #[test]
+    fn test_step_onto_consumes_projectile() {
+        let mut hazards = Hazards::new(
+            &HazardConfig {
+                fire_interval: Duration::from_secs(3600),
+                ..HazardConfig::default()
+            },
+            Instant::now(),
+        );
+        let buffer = Buffer::from(vec![String::from("abc")]);
+        let pos = Position { row: 0, col: 1 };
+        hazards.fire(
+            &buffer,
+            Projectile {
+                position: Position { row: 0, col: 2 },
+                heading: Heading::Left,
+            },
+        );
+
+        assert!(hazards.step_onto(pos));
+        assert!(!hazards.step_onto(pos));
+    }
+
+    #[test]
+    fn test_projectiles_move_once_per_step() {
+        let config = HazardConfig {
+            fire_interval: Duration::from_secs(3600),
+            ..HazardConfig::default()
+        };
+        let now = Instant::now();
+        let mut hazards = Hazards::new(&config, now);
+        let buffer = Buffer::from(vec![String::from("abcdef")]);
+        hazards.fire(
+            &buffer,
+            Projectile {
+                position: Position { row: 0, col: 0 },
+                heading: Heading::Right,
+            },
+        );
+        let cursor = Position { row: 0, col: 5 };
+
+        hazards.tick(
+            &buffer,
+            cursor,
+            now + config.projectile_step / 2,
+            &mut rand::rng(),
+        );
+        assert_eq!(hazards.projectiles()[0].position.col, 1);
+        hazards.tick(
+            &buffer,
+            cursor,
+            now + config.projectile_step,
+            &mut rand::rng(),
+        );
+        assert_eq!(hazards.projectiles()[0].position.col, 2);
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::domain::{Difficulty, data_dir, write_atomic};
+
+const HIGH_SCORES_FILE: &str = "highscores";
+/// Scores kept for each file, mode and difficulty.
+pub const MAX_HIGH_SCORES: usize = 10;
+/// Source of rounds played on generated code.
+pub const RANDOM_SOURCE: &str = "random";
+
+/// What a high score table is for: the code played, the mode and the difficulty.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScoreKey {
+    /// Hash of the file path, or `random` for generated code
+    pub source: String,
+    /// Name of the drill played, or `normal`
+    pub mode: String,
+    pub difficulty: Difficulty,
+}
+
+impl ScoreKey {
+    /// Builds the key of a round on `file_path` with `drill`, at `difficulty`.
+    pub fn new(file_path: Option<&str>, drill: Option<&str>, difficulty: Difficulty) -> Self {
+        Self {
+            source: file_path.map_or_else(|| RANDOM_SOURCE.to_string(), source_hash),
+            mode: drill.unwrap_or("normal").to_string(),
+            difficulty,
+        }
+    }
+}
+
+/// A score in a high score table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HighScore {
+    pub score: usize,
+    /// When the round was played, in seconds since the Unix epoch
+    pub date: u64,
+    pub keystrokes: usize,
+    /// Percentage of motions that hit an enemy
+    pub accuracy: usize,
+    /// File the round was played on, or `random`
+    pub file: String,
+}
+
+/// Best scores of every file, mode and difficulty, saved locally between sessions.
+///
+/// Stored as one tab separated line per score.
+#[derive(Debug, Default)]
+pub struct HighScores {
+    path: Option<PathBuf>,
+    scores: Vec<(ScoreKey, HighScore)>,
+}
+
+impl HighScores {
+    /// Loads high scores from the default location, starting fresh if there are none.
+    pub fn load_default() -> Self {
+        match data_dir() {
+            Some(dir) => Self::load(dir.join(HIGH_SCORES_FILE)),
+            None => Self::default(),
+        }
+    }
+
+    /// Loads high scores from `path`, ignoring malformed lines.
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path).unwrap_or_default();
+        let scores = contents.lines().filter_map(parse_line).collect();
+        Self {
+            path: Some(path),
+            scores,
+        }
+    }
+
+    /// Writes the high scores back to the file they were loaded from, replacing it
+    /// atomically so a crash mid-write never loses the old scores.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let contents: String = self
+            .scores
+            .iter()
+            .map(|(key, score)| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                    key.source,
+                    key.mode,
+                    key.difficulty,
+                    score.score,
+                    score.date,
+                    score.keystrokes,
+                    score.accuracy,
+                    score.file
+                )
+            })
+            .collect();
+        write_atomic(path, &contents)
+    }
+
+    /// Records a score, keeping the best ones of its table.
+    ///
+    /// Returns its rank in the table, counted from 0, if it made it.
+    pub fn record(&mut self, key: ScoreKey, score: HighScore) -> Option<usize> {
+        let rank = self
+            .top(&key)
+            .iter()
+            .take_while(|best| best.score >= score.score)
+            .count();
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+
+        self.scores.push((key.clone(), score));
+        // Scores equal to a previous one rank below it
+        self.scores.sort_by(|(a, x), (b, y)| {
+            a.cmp(b)
+                .then(y.score.cmp(&x.score))
+                .then(x.date.cmp(&y.date))
+        });
+        let mut kept = 0;
+        self.scores.retain(|(k, _)| {
+            if *k != key {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_HIGH_SCORES
+        });
+        Some(rank)
+    }
+
+    /// Returns the best scores of a table, best first.
+    pub fn top(&self, key: &ScoreKey) -> Vec<&HighScore> {
+        let mut scores: Vec<&HighScore> = self
+            .scores
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, score)| score)
+            .collect();
+        scores.sort_by(|a, b| b.score.cmp(&a.score).then(a.date.cmp(&b.date)));
+        scores
+    }
+
+    /// Returns the key of every table with a score.
+    pub fn keys(&self) -> Vec<ScoreKey> {
+        let mut keys: Vec<ScoreKey> = self.scores.iter().map(|(key, _)| key.clone()).collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+/// Hashes a file path with FNV-1a, which stays the same across builds.
+fn source_hash(path: &str) -> String {
+    // The same file reached through different paths shares its table
+    let path =
+        fs::canonicalize(path).map_or_else(|_| path.to_string(), |p| p.display().to_string());
+    let hash = path.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn parse_line(line: &str) -> Option<(ScoreKey, HighScore)> {
+    let mut fields = line.split('\t');
+    let key = ScoreKey {
+        source: fields.next()?.to_string(),
+        mode: fields.next()?.to_string(),
+        difficulty: Difficulty::from_name(fields.next()?)?,
+    };
+    let score = HighScore {
+        score: fields.next()?.parse().ok()?,
+        date: fields.next()?.parse().ok()?,
+        keystrokes: fields.next()?.parse().ok()?,
+        accuracy: fields.next()?.parse().ok()?,
+        file: fields.next()?.to_string(),
+    };
+    Some((key, score))
+}
+
+/// Formats seconds since the Unix epoch as a `YYYY-MM-DD` date, in UTC.
+pub fn format_date(secs: u64) -> String {
+    // Days to civil date, after Howard Hinnant's algorithm
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod highscores_tests {
+    use super::*;
+    use std::env;
+
+    fn score(score: usize, date: u64) -> HighScore {
+        HighScore {
+            score,
+            date,
+            keystrokes: 40,
+            accuracy: 75,
+            file: RANDOM_SOURCE.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tables_keep_the_best_scores() {
+        let mut scores = HighScores::default();
+        let key = ScoreKey::new(None, None, Difficulty::Normal);
+        for i in 0..MAX_HIGH_SCORES {
+            assert_eq!(scores.record(key.clone(), score(i * 10, i as u64)), Some(0));
+        }
+        assert_eq!(scores.record(key.clone(), score(0, 99)), None);
+        assert_eq!(scores.record(key.clone(), score(55, 99)), Some(4));
+
+        let top = scores.top(&key);
+        assert_eq!(top.len(), MAX_HIGH_SCORES);
+        assert_eq!(top[4].score, 55);
+        assert_eq!(top.last().unwrap().score, 10);
+
+        // Other modes and difficulties have tables of their own
+        let hard = ScoreKey::new(None, None, Difficulty::Hard);
+        assert_eq!(scores.record(hard.clone(), score(1, 0)), Some(0));
+        assert_eq!(scores.keys(), [key, hard]);
+    }
+
+    #[test]
+    fn test_high_scores_save_and_load() {
+        let dir = env::temp_dir().join(format!("hjkl-highscores-{}", std::process::id()));
+        let path = dir.join(HIGH_SCORES_FILE);
+
+        let mut scores = HighScores::load(&path);
+        let key = ScoreKey::new(Some("src/main.rs"), Some("Words only"), Difficulty::Easy);
+        scores.record(key.clone(), score(120, 1_700_000_000));
+        scores.save().unwrap();
+
+        let loaded = HighScores::load(&path);
+        assert_eq!(loaded.top(&key), [&score(120, 1_700_000_000)]);
+        // Only the scores file is left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14");
+    }
+}
@@ -0,0 +1,362 @@
+use std::{fmt::Display, time::Instant};
+
+use crate::domain::{
+    Buffer, Cursor, DEFAULT_MAX_COUNT, MotionSolver, Position,
+    motions::{Motion, MotionKind, MotionSet},
+};
+
+/// A lesson introducing one family of motions.
+pub struct Lesson {
+    /// Stable identifier used to save progress
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Lines of the explanation panel
+    pub explanation: &'static [&'static str],
+    /// Keys played back in the demonstration, starting from the top of the buffer
+    pub demo: &'static str,
+    /// Lines of the exercise buffer
+    pub lines: &'static [&'static str],
+    /// Positions to reach in the exercise, in order
+    pub targets: &'static [Position],
+    /// Motions the lesson is about, used to compute the par
+    pub motions: &'static [MotionKind],
+}
+
+/// The lesson curriculum, in the order lessons are unlocked.
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        id: "basic",
+        title: "Basic movement",
+        explanation: &[
+            "h moves left, l moves right.",
+            "j moves down, k moves up.",
+            "",
+            "Keep your fingers on the home row:",
+            "j looks like an arrow pointing down.",
+        ],
+        demo: "jjllllhhk",
+        lines: &[
+            "fn main() {",
+            "    let x = 1;",
+            "    let y = 2;",
+            "    println!(\"{}\", x + y);",
+            "}",
+        ],
+        targets: &[
+            Position { row: 1, col: 8 },
+            Position { row: 2, col: 8 },
+            Position { row: 3, col: 4 },
+            Position { row: 4, col: 0 },
+        ],
+        motions: &[
+            MotionKind::Left,
+            MotionKind::Down,
+            MotionKind::Up,
+            MotionKind::Right,
+        ],
+    },
+    Lesson {
+        id: "word",
+        title: "Word motions",
+        explanation: &[
+            "w jumps to the start of the next word.",
+            "e jumps to the end of the word.",
+            "b jumps back to the start of a word.",
+            "",
+            "Punctuation counts as its own word.",
+        ],
+        demo: "wwwbe",
+        lines: &[
+            "let message = format!(\"hello {}\", name);",
+            "let length = message.len();",
+            "print(message, length);",
+        ],
+        targets: &[
+            Position { row: 0, col: 14 },
+            Position { row: 1, col: 4 },
+            Position { row: 2, col: 15 },
+        ],
+        motions: &[
+            MotionKind::Left,
+            MotionKind::Down,
+            MotionKind::Up,
+            MotionKind::Right,
+            MotionKind::WordStart,
+            MotionKind::WordEnd,
+            MotionKind::WordBackward,
+        ],
+    },
+    Lesson {
+        id: "find",
+        title: "Find and till",
+        explanation: &[
+            "f{c} jumps onto the next c, F{c} the previous.",
+            "t{c} stops right before the next c, T{c} after the previous.",
+            "",
+            "; repeats the last find or till,",
+            ", repeats it in the other direction.",
+        ],
+        demo: "f(f,;F(",
+        lines: &[
+            "let total = compute(alpha, beta, gamma);",
+            "if total > limit { report(total); }",
+        ],
+        targets: &[
+            Position { row: 0, col: 19 },
+            Position { row: 0, col: 31 },
+            Position { row: 1, col: 25 },
+            Position { row: 1, col: 34 },
+        ],
+        motions: &[
+            MotionKind::Left,
+            MotionKind::Down,
+            MotionKind::Up,
+            MotionKind::Right,
+            MotionKind::FindNextChar,
+            MotionKind::FindPrevChar,
+            MotionKind::TillNextChar,
+            MotionKind::TillPrevChar,
+            MotionKind::RepeatFind,
+            MotionKind::RepeatFindReverse,
+        ],
+    },
+    Lesson {
+        id: "counts",
+        title: "Counts",
+        explanation: &[
+            "Type a number before a motion to repeat it.",
+            "4j moves down four lines, 3w skips three words.",
+            "",
+            "Counts also work with f, F, t and T:",
+            "2f, jumps to the second comma.",
+        ],
+        demo: "4j3w2k",
+        lines: &[
+            "struct Config {",
+            "    name: String,",
+            "    port: u16,",
+            "    host: String,",
+            "    retries: u32,",
+            "    timeout: u64,",
+            "    verbose: bool,",
+            "}",
+        ],
+        targets: &[
+            Position { row: 5, col: 4 },
+            Position { row: 5, col: 13 },
+            Position { row: 1, col: 10 },
+            Position { row: 7, col: 0 },
+        ],
+        motions: &MotionKind::ALL,
+    },
+];
+
+/// How well an exercise was completed, compared to the par keystrokes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Grade {
+    Passed,
+    Great,
+    Perfect,
+}
+
+impl Grade {
+    /// Grades `keystrokes` against the minimum `par`.
+    pub fn from_keystrokes(keystrokes: usize, par: usize) -> Self {
+        if keystrokes <= par {
+            Grade::Perfect
+        } else if keystrokes * 2 <= par * 3 {
+            Grade::Great
+        } else {
+            Grade::Passed
+        }
+    }
+
+    /// Parses a grade from its name, as written by `Display`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "passed" => Some(Grade::Passed),
+            "great" => Some(Grade::Great),
+            "perfect" => Some(Grade::Perfect),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Grade::Passed => "passed",
+            Grade::Great => "great",
+            Grade::Perfect => "perfect",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The current step of a lesson.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LessonPhase {
+    /// Showing the explanation panel
+    Explanation,
+    /// Playing back the demonstration keys
+    Demo,
+    /// The player is solving the exercise
+    Exercise,
+    /// The exercise is done
+    Result(Grade),
+}
+
+/// The graded exercise of a lesson: reach every target in order.
+pub struct Exercise {
+    buffer: Buffer,
+    cursor: Cursor,
+    targets: Vec<Position>,
+    /// Number of targets reached so far
+    reached: usize,
+    keystrokes: usize,
+    /// Fewest keystrokes needed to reach every target
+    par: usize,
+}
+
+impl Exercise {
+    pub fn new(lesson: &Lesson) -> Self {
+        let buffer: Buffer = lesson
+            .lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .into();
+
+        // Par is the sum of the cheapest routes between consecutive targets
+        let mut solver = MotionSolver::new(MotionSet::only(lesson.motions), DEFAULT_MAX_COUNT);
+        let mut par = 0;
+        let mut from = Position::default();
+        for &target in lesson.targets {
+            if let Some(route) = solver.solve(&buffer, from, target) {
+                par += route.cost;
+            }
+            from = target;
+        }
+
+        Self {
+            buffer,
+            cursor: Cursor::default(),
+            targets: lesson.targets.to_vec(),
+            reached: 0,
+            keystrokes: 0,
+            par,
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// Returns the target to reach next, if any.
+    pub fn target(&self) -> Option<Position> {
+        self.targets.get(self.reached).copied()
+    }
+
+    /// Returns the number of targets reached and the total number of targets.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.reached, self.targets.len())
+    }
+
+    pub fn keystrokes(&self) -> usize {
+        self.keystrokes
+    }
+
+    pub fn par(&self) -> usize {
+        self.par
+    }
+
+    /// Counts a key typed by the player.
+    pub fn record_keystroke(&mut self) {
+        self.keystrokes += 1;
+    }
+
+    /// Applies motion to the cursor at `now`, advancing to the next target when reached
+    pub fn apply_motion(&mut self, motion: Motion, count: Option<usize>, now: Instant) {
+        self.cursor.apply_motion(&self.buffer, motion, count, now);
+        if self.target() == Some(self.cursor.pos()) {
+            self.reached += 1;
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.reached == self.targets.len()
+    }
+
+    /// Returns the grade of the exercise once every target is reached.
+    pub fn grade(&self) -> Option<Grade> {
+        self.is_complete()
+            .then(|| Grade::from_keystrokes(self.keystrokes, self.par))
+    }
+}
+
+#[cfg(test)]
+mod lesson_tests {
+    use super::*;
+
+    #[test]
+    fn test_lesson_targets_are_reachable() {
+        for lesson in LESSONS {
+            let exercise = Exercise::new(lesson);
+            let mut solver = MotionSolver::new(MotionSet::only(lesson.motions), DEFAULT_MAX_COUNT);
+            let mut from = Position::default();
+
+            for &target in lesson.targets {
+                assert!(
+                    exercise.buffer().get_char(&target).is_some(),
+                    "{}: {:?} is outside the buffer",
+                    lesson.id,
+                    target
+                );
+                assert!(
+                    solver.solve(exercise.buffer(), from, target).is_some(),
+                    "{}: {:?} is unreachable",
+                    lesson.id,
+                    target
+                );
+                from = target;
+            }
+        }
+    }
+
+    #[test]
+    fn test_exercise_grade() {
+        let mut exercise = Exercise::new(&LESSONS[0]);
+        let par = exercise.par();
+
+        // Follow the cheapest route to each target
+        let mut solver = MotionSolver::new(MotionSet::only(LESSONS[0].motions), DEFAULT_MAX_COUNT);
+        while let Some(target) = exercise.target() {
+            assert_eq!(exercise.grade(), None);
+            let route = solver
+                .solve(exercise.buffer(), exercise.cursor().pos(), target)
+                .unwrap();
+            for _ in 0..route.cost {
+                exercise.record_keystroke();
+            }
+            for step in route.steps {
+                if let crate::domain::Step::Motion { motion, count } = step {
+                    exercise.apply_motion(motion, count, Instant::now());
+                }
+            }
+        }
+
+        assert_eq!(exercise.keystrokes(), par);
+        assert_eq!(exercise.grade(), Some(Grade::Perfect));
+    }
+
+    #[test]
+    fn test_grade_from_keystrokes() {
+        assert_eq!(Grade::from_keystrokes(10, 10), Grade::Perfect);
+        assert_eq!(Grade::from_keystrokes(15, 10), Grade::Great);
+        assert_eq!(Grade::from_keystrokes(16, 10), Grade::Passed);
+    }
+}
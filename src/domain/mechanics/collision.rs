@@ -1,19 +1,51 @@
-use crate::domain::{Position, enemies::Enemies};
+use crate::domain::{
+    Position,
+    enemies::{Enemies, EnemyKind},
+    motions::Motion,
+};
 
 /// Events related to collisions in the game world.
 #[derive(Debug, Clone)]
 pub enum CollisionEvent {
     /// Event when the player hits an enemy.
-    PlayerHitEnemy { position: Position, enemy_id: usize },
+    PlayerHitEnemy {
+        position: Position,
+        enemy_id: usize,
+        kind: EnemyKind,
+    },
+    /// Event when the player lands on an enemy with a motion it is armored against,
+    /// or repeats a hit on a boss.
+    PlayerBounced {
+        position: Position,
+        enemy_id: usize,
+        kind: EnemyKind,
+    },
 }
 
-pub fn check_collisions(cursor_pos: Position, enemies: &Enemies) -> Vec<CollisionEvent> {
+/// Checks which enemies the cursor landed on after moving with `motion`.
+pub fn check_collisions(
+    cursor_pos: Position,
+    motion: &Motion,
+    enemies: &Enemies,
+) -> Vec<CollisionEvent> {
     enemies
         .iter()
-        .filter(|enemy| enemy.pos() == cursor_pos)
-        .map(|enemy| CollisionEvent::PlayerHitEnemy {
-            position: enemy.pos(),
-            enemy_id: enemy.id().id(),
+        .filter(|enemy| enemy.occupies(cursor_pos))
+        .map(|enemy| {
+            let (position, enemy_id, kind) = (cursor_pos, enemy.id().id(), enemy.kind());
+            if enemy.accepts_hit(cursor_pos, motion) {
+                CollisionEvent::PlayerHitEnemy {
+                    position,
+                    enemy_id,
+                    kind,
+                }
+            } else {
+                CollisionEvent::PlayerBounced {
+                    position,
+                    enemy_id,
+                    kind,
+                }
+            }
         })
         .collect()
 }
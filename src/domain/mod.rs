@@ -1,20 +1,49 @@
+pub mod achievements;
 pub mod buffer;
+pub mod challenge;
+pub mod clock;
 pub mod codegen;
 pub mod config;
 pub mod cursor;
+pub mod drill;
+pub mod edit;
 pub mod enemies;
 pub mod events;
+pub mod hazards;
+pub mod highscores;
+pub mod lesson;
 pub mod mechanics;
 pub mod motions;
 pub mod position;
+pub mod powerups;
+pub mod progress;
+pub mod scenario;
+pub mod scoring;
+pub mod solver;
+pub mod stats;
+pub mod syntax;
 pub mod types;
 pub mod world;
 
+pub use achievements::*;
 pub use buffer::*;
+pub use challenge::*;
+pub use clock::*;
 pub use codegen::*;
 pub use config::*;
 pub use cursor::*;
+pub use drill::*;
+pub use edit::*;
 pub use events::*;
+pub use hazards::*;
+pub use highscores::*;
+pub use lesson::*;
 pub use position::*;
+pub use powerups::*;
+pub use progress::*;
+pub use scenario::*;
+pub use scoring::*;
+pub use solver::*;
+pub use stats::*;
 pub use types::*;
 pub use world::World;
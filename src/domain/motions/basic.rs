@@ -10,7 +10,7 @@ pub fn h_motion(buffer: &Buffer, mut position: Position, count: usize) -> Positi
     position
 }
 
-/// Moves the cursor up by `count` lines.
+/// Moves the cursor down by `count` lines.
 pub fn j_motion(buffer: &Buffer, mut position: Position, count: usize) -> Position {
     for _ in 0..count {
         if !position.step_line(buffer, Direction::Forward) {
@@ -20,7 +20,7 @@ pub fn j_motion(buffer: &Buffer, mut position: Position, count: usize) -> Positi
     position
 }
 
-/// Moves the cursor down by `count` lines.
+/// Moves the cursor up by `count` lines.
 pub fn k_motion(buffer: &Buffer, mut position: Position, count: usize) -> Position {
     for _ in 0..count {
         if !position.step_line(buffer, Direction::Backward) {
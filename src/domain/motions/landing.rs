@@ -0,0 +1,43 @@
+use std::collections::HashSet;
+
+use crate::domain::{Buffer, Position, motions::words::word_boundaries};
+
+/// Returns the cells that common motions land on directly.
+///
+/// These are word starts and ends, reached with `w`, `b` and `e`, and characters
+/// unique on their line, reached with a single `f` or `F`.
+pub fn landing_points(buffer: &Buffer) -> HashSet<Position> {
+    let mut points = HashSet::new();
+
+    for (row, line) in buffer.lines().iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        for (col, c) in chars.iter().enumerate() {
+            let is_word_edge =
+                word_boundaries(line, col).is_some_and(|(start, end)| start == col || end == col);
+            let is_unique = !c.is_whitespace() && chars.iter().filter(|&o| o == c).count() == 1;
+
+            if is_word_edge || is_unique {
+                points.insert(Position { row, col });
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod landing_tests {
+    use super::*;
+
+    #[test]
+    fn test_landing_points() {
+        let buffer = Buffer::from(vec![String::from("abcd xax")]);
+        let points = landing_points(&buffer);
+        let at = |col| Position { row: 0, col };
+
+        assert!(points.contains(&at(0))); // word start
+        assert!(points.contains(&at(3))); // word end
+        assert!(points.contains(&at(1))); // unique 'b'
+        assert!(!points.contains(&at(4))); // space
+        assert!(!points.contains(&at(6))); // 'a' appears twice, mid-word
+    }
+}
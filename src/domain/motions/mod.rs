@@ -1,6 +1,11 @@
 mod basic;
 mod jumps;
+mod landing;
 mod motion;
+mod set;
 mod words;
 
+pub use landing::landing_points;
 pub use motion::Motion;
+pub use set::{MotionFamily, MotionKind, MotionSet};
+pub use words::word_boundaries;
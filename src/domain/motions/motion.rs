@@ -1,10 +1,12 @@
+use std::fmt::Display;
+
 use crate::{
-    domain::motions::{basic::*, jumps::*, words::*},
+    domain::motions::{MotionKind, basic::*, jumps::*, words::*},
     domain::{Buffer, Position},
 };
 
 /// Represents different Vim Motions
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Motion {
     Left,  // h
     Down,  // j
@@ -27,6 +29,57 @@ pub enum Motion {
 }
 
 impl Motion {
+    /// Returns the kind of this motion, without its target character.
+    pub fn kind(&self) -> MotionKind {
+        match self {
+            Motion::Left => MotionKind::Left,
+            Motion::Down => MotionKind::Down,
+            Motion::Up => MotionKind::Up,
+            Motion::Right => MotionKind::Right,
+            Motion::WordStart => MotionKind::WordStart,
+            Motion::WordEnd => MotionKind::WordEnd,
+            Motion::WordBackward => MotionKind::WordBackward,
+            Motion::FindNextChar(_) => MotionKind::FindNextChar,
+            Motion::FindPrevChar(_) => MotionKind::FindPrevChar,
+            Motion::TillNextChar(_) => MotionKind::TillNextChar,
+            Motion::TillPrevChar(_) => MotionKind::TillPrevChar,
+        }
+    }
+
+    /// Builds a find/till motion of the given kind targeting `c`.
+    ///
+    /// Returns None if the kind does not take a target character.
+    pub fn with_target(kind: MotionKind, c: char) -> Option<Motion> {
+        match kind {
+            MotionKind::FindNextChar => Some(Motion::FindNextChar(c)),
+            MotionKind::FindPrevChar => Some(Motion::FindPrevChar(c)),
+            MotionKind::TillNextChar => Some(Motion::TillNextChar(c)),
+            MotionKind::TillPrevChar => Some(Motion::TillPrevChar(c)),
+            _ => None,
+        }
+    }
+
+    /// Builds a motion of the given kind that does not take a target character.
+    ///
+    /// Returns None for find/till and repeat kinds.
+    pub fn from_kind(kind: MotionKind) -> Option<Motion> {
+        match kind {
+            MotionKind::Left => Some(Motion::Left),
+            MotionKind::Down => Some(Motion::Down),
+            MotionKind::Up => Some(Motion::Up),
+            MotionKind::Right => Some(Motion::Right),
+            MotionKind::WordStart => Some(Motion::WordStart),
+            MotionKind::WordEnd => Some(Motion::WordEnd),
+            MotionKind::WordBackward => Some(Motion::WordBackward),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of keys needed to type this motion, without a count.
+    pub fn key_len(&self) -> usize {
+        if self.needs_target() { 2 } else { 1 }
+    }
+
     pub fn is_find_till(&self) -> bool {
         matches!(
             self,
@@ -65,8 +118,8 @@ impl Motion {
     pub fn apply(&self, buffer: &Buffer, position: Position, count: usize) -> Position {
         match self {
             Motion::Left => h_motion(buffer, position, count),
-            Motion::Down => k_motion(buffer, position, count),
-            Motion::Up => j_motion(buffer, position, count),
+            Motion::Down => j_motion(buffer, position, count),
+            Motion::Up => k_motion(buffer, position, count),
             Motion::Right => l_motion(buffer, position, count),
             Motion::WordStart => w_motion(buffer, position, count),
             Motion::WordEnd => e_motion(buffer, position, count),
@@ -78,3 +131,33 @@ impl Motion {
         }
     }
 }
+
+impl Display for Motion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Motion::FindNextChar(c)
+            | Motion::FindPrevChar(c)
+            | Motion::TillNextChar(c)
+            | Motion::TillPrevChar(c) => write!(f, "{}{}", self.kind().key(), c),
+            _ => write!(f, "{}", self.kind().key()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod motion_tests {
+    use super::*;
+
+    #[test]
+    fn test_vertical_motions_direction() {
+        let buffer = Buffer::from(vec![
+            String::from("a"),
+            String::from("b"),
+            String::from("c"),
+        ]);
+        let middle = Position { row: 1, col: 0 };
+
+        assert_eq!(Motion::Down.apply(&buffer, middle, 1).row, 2);
+        assert_eq!(Motion::Up.apply(&buffer, middle, 1).row, 0);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use crate::domain::motions::Motion;
+
+/// The kind of a motion, ignoring its count and target character.
+///
+/// Also covers the `;` and `,` repeat keys, which are not motions on their own
+/// but replay the last find/till motion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MotionKind {
+    Left,              // h
+    Down,              // j
+    Up,                // k
+    Right,             // l
+    WordStart,         // w
+    WordEnd,           // e
+    WordBackward,      // b
+    FindNextChar,      // f
+    FindPrevChar,      // F
+    TillNextChar,      // t
+    TillPrevChar,      // T
+    RepeatFind,        // ;
+    RepeatFindReverse, // ,
+}
+
+impl MotionKind {
+    pub const ALL: [MotionKind; 13] = [
+        MotionKind::Left,
+        MotionKind::Down,
+        MotionKind::Up,
+        MotionKind::Right,
+        MotionKind::WordStart,
+        MotionKind::WordEnd,
+        MotionKind::WordBackward,
+        MotionKind::FindNextChar,
+        MotionKind::FindPrevChar,
+        MotionKind::TillNextChar,
+        MotionKind::TillPrevChar,
+        MotionKind::RepeatFind,
+        MotionKind::RepeatFindReverse,
+    ];
+
+    /// Returns the key that triggers this kind of motion.
+    pub fn key(self) -> char {
+        match self {
+            MotionKind::Left => 'h',
+            MotionKind::Down => 'j',
+            MotionKind::Up => 'k',
+            MotionKind::Right => 'l',
+            MotionKind::WordStart => 'w',
+            MotionKind::WordEnd => 'e',
+            MotionKind::WordBackward => 'b',
+            MotionKind::FindNextChar => 'f',
+            MotionKind::FindPrevChar => 'F',
+            MotionKind::TillNextChar => 't',
+            MotionKind::TillPrevChar => 'T',
+            MotionKind::RepeatFind => ';',
+            MotionKind::RepeatFindReverse => ',',
+        }
+    }
+
+    /// Returns the family this kind belongs to.
+    pub fn family(self) -> MotionFamily {
+        match self {
+            MotionKind::Left | MotionKind::Down | MotionKind::Up | MotionKind::Right => {
+                MotionFamily::Basic
+            }
+            MotionKind::WordStart | MotionKind::WordEnd | MotionKind::WordBackward => {
+                MotionFamily::Word
+            }
+            MotionKind::FindNextChar
+            | MotionKind::FindPrevChar
+            | MotionKind::TillNextChar
+            | MotionKind::TillPrevChar
+            | MotionKind::RepeatFind
+            | MotionKind::RepeatFindReverse => MotionFamily::Find,
+        }
+    }
+
+    /// Returns true if this kind needs a target character (f, F, t, T).
+    pub fn needs_target(self) -> bool {
+        matches!(
+            self,
+            MotionKind::FindNextChar
+                | MotionKind::FindPrevChar
+                | MotionKind::TillNextChar
+                | MotionKind::TillPrevChar
+        )
+    }
+}
+
+/// A group of related motions, such as the word motions `w`, `e` and `b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MotionFamily {
+    /// h, j, k, l
+    Basic,
+    /// w, e, b
+    Word,
+    /// f, F, t, T and their `;` and `,` repeats
+    Find,
+}
+
+impl MotionFamily {
+    pub const ALL: [MotionFamily; 3] =
+        [MotionFamily::Basic, MotionFamily::Word, MotionFamily::Find];
+
+    /// Returns the keys of the motions in this family.
+    pub fn keys(self) -> &'static str {
+        match self {
+            MotionFamily::Basic => "hjkl",
+            MotionFamily::Word => "web",
+            MotionFamily::Find => "fFtT;,",
+        }
+    }
+}
+
+/// A set of allowed motion kinds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MotionSet(HashSet<MotionKind>);
+
+impl Default for MotionSet {
+    fn default() -> Self {
+        MotionSet::all()
+    }
+}
+
+impl MotionSet {
+    /// Returns a set containing every supported motion kind.
+    pub fn all() -> Self {
+        MotionSet(MotionKind::ALL.into_iter().collect())
+    }
+
+    /// Returns a set containing only the given motion kinds.
+    pub fn only(kinds: &[MotionKind]) -> Self {
+        MotionSet(kinds.iter().copied().collect())
+    }
+
+    pub fn contains(&self, kind: MotionKind) -> bool {
+        self.0.contains(&kind)
+    }
+
+    /// Returns true if the given motion is allowed by this set.
+    pub fn allows(&self, motion: &Motion) -> bool {
+        self.contains(motion.kind())
+    }
+
+    pub fn insert(&mut self, kind: MotionKind) {
+        self.0.insert(kind);
+    }
+
+    pub fn remove(&mut self, kind: MotionKind) {
+        self.0.remove(&kind);
+    }
+
+    /// Returns the allowed kinds in the canonical `MotionKind::ALL` order.
+    pub fn kinds(&self) -> impl Iterator<Item = MotionKind> + '_ {
+        MotionKind::ALL.into_iter().filter(|k| self.0.contains(k))
+    }
+}
@@ -14,10 +14,10 @@ impl Position {
     ///
     /// Returns true if the move was successful
     pub fn step_char(&mut self, buffer: &Buffer, direction: Direction) -> bool {
-        if let Some(line) = buffer.get_line(self.row) {
+        if self.row < buffer.rows() {
             match direction {
                 Direction::Forward => {
-                    if self.col + 1 < line.len() {
+                    if self.col + 1 < buffer.get_line_len(self.row) {
                         self.col += 1;
                         true
                     } else if self.row + 1 < buffer.rows() {
@@ -34,11 +34,7 @@ impl Position {
                         true
                     } else if self.row > 0 {
                         self.row -= 1;
-                        if let Some(prev_line) = buffer.get_line(self.row) {
-                            self.col = prev_line.len().saturating_sub(1);
-                        } else {
-                            self.col = 0;
-                        }
+                        self.col = buffer.get_line_len(self.row).saturating_sub(1);
                         true
                     } else {
                         false
@@ -74,8 +70,7 @@ impl Position {
             Direction::Forward => {
                 if self.row + 1 < buffer.rows() {
                     self.row += 1;
-                    let next_line = buffer.get_line(self.row).expect("Row should exist");
-                    let line_len = next_line.len();
+                    let line_len = buffer.get_line_len(self.row);
 
                     // Adjust column if out of bounds
                     self.col = self.col.min(line_len.saturating_sub(1));
@@ -87,8 +82,7 @@ impl Position {
             Direction::Backward => {
                 if self.row > 0 {
                     self.row -= 1;
-                    let prev_line = buffer.get_line(self.row).expect("Row should exist");
-                    let line_len = prev_line.len();
+                    let line_len = buffer.get_line_len(self.row);
 
                     // Adjust column if out of bounds
                     self.col = self.col.min(line_len.saturating_sub(1));
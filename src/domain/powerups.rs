@@ -0,0 +1,244 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::domain::{Buffer, Position, PowerUpConfig};
+
+/// The kinds of power-ups the player can collect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    /// Stops every enemy for a while
+    Freeze,
+    /// Clears the enemies of the current paragraph at once
+    Bomb,
+    /// Pulls enemies toward the cursor for a while
+    Magnet,
+    /// Doubles the points earned for a while
+    DoubleScore,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Freeze,
+        PowerUpKind::Bomb,
+        PowerUpKind::Magnet,
+        PowerUpKind::DoubleScore,
+    ];
+
+    /// Returns the glyph drawn for this kind.
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            PowerUpKind::Freeze => "❄",
+            PowerUpKind::Bomb => "💣",
+            PowerUpKind::Magnet => "🧲",
+            PowerUpKind::DoubleScore => "💎",
+        }
+    }
+
+    /// Returns how long the effect lasts, or None if it acts at once.
+    pub fn duration(&self, config: &PowerUpConfig) -> Option<Duration> {
+        match self {
+            PowerUpKind::Freeze => Some(config.freeze_duration),
+            PowerUpKind::Bomb => None,
+            PowerUpKind::Magnet => Some(config.magnet_duration),
+            PowerUpKind::DoubleScore => Some(config.double_score_duration),
+        }
+    }
+}
+
+impl Display for PowerUpKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PowerUpKind::Freeze => "Freeze",
+            PowerUpKind::Bomb => "Bomb",
+            PowerUpKind::Magnet => "Magnet",
+            PowerUpKind::DoubleScore => "Double score",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A power-up lying on the buffer, waiting to be collected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub position: Position,
+}
+
+/// Power-ups on the buffer and the timed effects currently active.
+pub struct PowerUps {
+    items: Vec<PowerUp>,
+    /// Active effects mapped to when they wear off
+    active: HashMap<PowerUpKind, Instant>,
+    last_spawned: Instant,
+    /// Last time the magnet pulled enemies in
+    last_pulled: Instant,
+    config: PowerUpConfig,
+}
+
+impl PowerUps {
+    pub fn new(config: &PowerUpConfig, now: Instant) -> Self {
+        Self {
+            items: vec![],
+            active: HashMap::new(),
+            last_spawned: now,
+            last_pulled: now,
+            config: config.clone(),
+        }
+    }
+
+    pub fn items(&self) -> &[PowerUp] {
+        &self.items
+    }
+
+    /// Returns true if the timed effect of `kind` is active.
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.active.contains_key(&kind)
+    }
+
+    /// Returns the active effects with their time remaining at `now`, in `PowerUpKind::ALL` order.
+    pub fn active(&self, now: Instant) -> Vec<(PowerUpKind, Duration)> {
+        PowerUpKind::ALL
+            .into_iter()
+            .filter_map(|kind| Some((kind, self.active.get(&kind)?.saturating_duration_since(now))))
+            .collect()
+    }
+
+    /// Spawns a power-up now and then, and wears off expired effects.
+    ///
+    /// Returns the kinds whose effect wore off.
+    pub fn tick(
+        &mut self,
+        buffer: &Buffer,
+        cursor: Position,
+        now: Instant,
+        rng: &mut impl Rng,
+    ) -> Vec<PowerUpKind> {
+        if now.duration_since(self.last_spawned) >= self.config.spawn_interval {
+            self.last_spawned = now;
+            if self.items.len() < self.config.max_items
+                && let Some(position) = buffer.random_position(false, rng)
+                && position != cursor
+                && let Some(kind) = PowerUpKind::ALL.choose(rng)
+            {
+                self.items.push(PowerUp {
+                    kind: *kind,
+                    position,
+                });
+            }
+        }
+
+        let expired: Vec<PowerUpKind> = PowerUpKind::ALL
+            .into_iter()
+            .filter(|kind| self.active.get(kind).is_some_and(|until| *until <= now))
+            .collect();
+        for kind in &expired {
+            self.active.remove(kind);
+        }
+        expired
+    }
+
+    /// Collects the power-up at `pos`, starting its effect if it is timed.
+    pub fn collect_at(&mut self, pos: Position, now: Instant) -> Option<PowerUpKind> {
+        let idx = self.items.iter().position(|item| item.position == pos)?;
+        let kind = self.items.remove(idx).kind;
+        if let Some(duration) = kind.duration(&self.config) {
+            self.active.insert(kind, now + duration);
+        }
+        Some(kind)
+    }
+
+    /// Returns true if the magnet is active and due to pull enemies one step closer.
+    pub fn should_pull(&mut self, now: Instant) -> bool {
+        if self.is_active(PowerUpKind::Magnet)
+            && now.duration_since(self.last_pulled) >= self.config.magnet_step
+        {
+            self.last_pulled = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the factor applied to points earned.
+    pub fn score_multiplier(&self) -> usize {
+        if self.is_active(PowerUpKind::DoubleScore) {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod powerups_tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_starts_timed_effect() {
+        let now = Instant::now();
+        let mut powerups = PowerUps::new(&PowerUpConfig::default(), now);
+        let position = Position { row: 0, col: 3 };
+        powerups.items.push(PowerUp {
+            kind: PowerUpKind::DoubleScore,
+            position,
+        });
+
+        assert_eq!(powerups.collect_at(Position::default(), now), None);
+        assert_eq!(
+            powerups.collect_at(position, now),
+            Some(PowerUpKind::DoubleScore)
+        );
+        assert!(powerups.items().is_empty());
+        assert_eq!(powerups.score_multiplier(), 2);
+    }
+
+    #[test]
+    fn test_bomb_is_not_timed() {
+        let now = Instant::now();
+        let mut powerups = PowerUps::new(&PowerUpConfig::default(), now);
+        let position = Position::default();
+        powerups.items.push(PowerUp {
+            kind: PowerUpKind::Bomb,
+            position,
+        });
+
+        assert_eq!(powerups.collect_at(position, now), Some(PowerUpKind::Bomb));
+        assert!(powerups.active(now).is_empty());
+    }
+
+    #[test]
+    fn test_timed_effect_wears_off() {
+        let config = PowerUpConfig::default();
+        let now = Instant::now();
+        let mut powerups = PowerUps::new(&config, now);
+        let buffer = Buffer::from(vec![String::from("abc")]);
+        let position = Position::default();
+        powerups.items.push(PowerUp {
+            kind: PowerUpKind::Freeze,
+            position,
+        });
+        powerups.collect_at(position, now);
+
+        let almost = now + config.freeze_duration - Duration::from_millis(1);
+        assert!(
+            powerups
+                .tick(&buffer, position, almost, &mut rand::rng())
+                .is_empty()
+        );
+        assert!(powerups.is_active(PowerUpKind::Freeze));
+
+        let expired = powerups.tick(
+            &buffer,
+            position,
+            now + config.freeze_duration,
+            &mut rand::rng(),
+        );
+        assert_eq!(expired, vec![PowerUpKind::Freeze]);
+        assert!(!powerups.is_active(PowerUpKind::Freeze));
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::domain::{Grade, LESSONS};
+
+const PROGRESS_FILE: &str = "lessons";
+
+/// Returns the directory the game stores its data in, `$XDG_DATA_HOME/hjkl`
+/// falling back to `~/.local/share/hjkl`.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("hjkl"))
+}
+
+/// Replaces the file at `path` with `contents`, creating its directory if needed.
+///
+/// The file is written aside under a name of this process, synced, and renamed
+/// over the old one, so a crash mid-write leaves either the old contents or the new
+/// ones, and two sessions saving at once never write to the same file.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let temp = path.with_extension(format!("{}.tmp", process::id()));
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|_| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(err);
+    }
+
+    sync_dir(path)
+}
+
+/// Syncs the directory of `path`, making a rename into it durable.
+///
+/// Directories cannot be opened as files on Windows, where renames need no sync.
+fn sync_dir(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(dir) if cfg!(unix) && !dir.as_os_str().is_empty() => File::open(dir)?.sync_all(),
+        _ => Ok(()),
+    }
+}
+
+/// Best grades of completed lessons, saved locally between sessions.
+///
+/// Stored as one `<lesson id> <grade>` pair per line.
+#[derive(Debug, Default)]
+pub struct LessonProgress {
+    path: Option<PathBuf>,
+    grades: HashMap<String, Grade>,
+}
+
+impl LessonProgress {
+    /// Loads progress from the default location, starting fresh if there is none.
+    pub fn load_default() -> Self {
+        match data_dir() {
+            Some(dir) => Self::load(dir.join(PROGRESS_FILE)),
+            None => Self::default(),
+        }
+    }
+
+    /// Loads progress from `path`, ignoring malformed lines.
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path).unwrap_or_default();
+
+        let grades = contents
+            .lines()
+            .filter_map(|line| {
+                let (id, grade) = line.split_once(' ')?;
+                Some((id.to_string(), Grade::from_name(grade.trim())?))
+            })
+            .collect();
+
+        Self {
+            path: Some(path),
+            grades,
+        }
+    }
+
+    /// Writes progress back to the file it was loaded from, replacing it atomically.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut ids: Vec<&String> = self.grades.keys().collect();
+        ids.sort();
+        let contents: String = ids
+            .into_iter()
+            .map(|id| format!("{} {}\n", id, self.grades[id]))
+            .collect();
+        write_atomic(path, &contents)
+    }
+
+    /// Returns the best grade of a lesson, if completed.
+    pub fn grade(&self, id: &str) -> Option<Grade> {
+        self.grades.get(id).copied()
+    }
+
+    /// Records a completed lesson, keeping the best grade.
+    pub fn record(&mut self, id: &str, grade: Grade) {
+        let best = self.grades.entry(id.to_string()).or_insert(grade);
+        *best = (*best).max(grade);
+    }
+
+    /// Returns true if the lesson at `index` can be played: the first lesson
+    /// always is, later ones once the previous lesson is completed.
+    pub fn is_unlocked(&self, index: usize) -> bool {
+        index == 0
+            || LESSONS
+                .get(index - 1)
+                .is_some_and(|prev| self.grades.contains_key(prev.id))
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_save_and_load() {
+        let path = env::temp_dir().join(format!("hjkl-progress-{}", process::id()));
+
+        let mut progress = LessonProgress::load(&path);
+        assert!(!progress.is_unlocked(1));
+
+        progress.record("basic", Grade::Great);
+        progress.record("basic", Grade::Passed);
+        progress.save().unwrap();
+        assert!(
+            !path
+                .with_extension(format!("{}.tmp", process::id()))
+                .exists()
+        );
+
+        let loaded = LessonProgress::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.grade("basic"), Some(Grade::Great));
+        assert!(loaded.is_unlocked(1));
+        assert!(!loaded.is_unlocked(2));
+    }
+}
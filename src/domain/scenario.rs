@@ -0,0 +1,209 @@
+use std::{fs, io, path::Path};
+
+use crate::domain::{Buffer, Position, enemies::EnemyKind};
+
+/// Extension of scenario files.
+pub const SCENARIO_EXTENSION: &str = "scenario";
+/// Marks the cursor start within the buffer text.
+pub const CURSOR_MARKER: &str = "$0";
+const BUFFER_MARKER: &str = "=== buffer";
+
+/// A fixed setup of the world: buffer, cursor start and enemies standing still.
+///
+/// Scenarios are played as custom levels, and double as motion fixtures when
+/// they give the keys to press and where the cursor should end up. They are
+/// plain text files with `key: value` headers, followed by the buffer after a
+/// marker line, with the cursor start marked by `$0`. Positions are written
+/// `row:col`, counted from 0, and trailing blank lines of the buffer are ignored:
+///
+/// ```text
+/// title: w skips a run of punctuation
+/// keys: w
+/// expect: 0:9
+/// enemy: 1:4
+/// enemy: 1:10 armored-find
+/// === buffer
+/// $0foo.bar::<T>();
+/// let x = foo;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scenario {
+    pub title: String,
+    pub lines: Vec<String>,
+    /// Where the cursor starts
+    pub cursor: Position,
+    /// Keys to press, in Vim notation such as `2w` or `fx<Esc>`
+    pub keys: String,
+    /// Where the cursor should be once the keys are pressed, if checked
+    pub expect: Option<Position>,
+    /// Enemies placed on the buffer, which never move
+    pub enemies: Vec<(Position, EnemyKind)>,
+}
+
+impl Scenario {
+    /// Parses a scenario from the contents of a scenario file.
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let (header, text) = contents
+            .split_once(BUFFER_MARKER)
+            .ok_or_else(|| invalid(format!("Missing '{}' section", BUFFER_MARKER)))?;
+
+        let mut scenario = Scenario {
+            title: "Untitled".to_string(),
+            lines: vec![],
+            cursor: Position::default(),
+            keys: String::new(),
+            expect: None,
+            enemies: vec![],
+        };
+        for line in header.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid(format!("Invalid header line '{}'", line)))?;
+            let value = value.trim();
+            match key.trim() {
+                "title" => scenario.title = value.to_string(),
+                "keys" => scenario.keys = value.to_string(),
+                "expect" => {
+                    let pos = parse_position(value)
+                        .ok_or_else(|| invalid(format!("Invalid position '{}'", value)))?;
+                    scenario.expect = Some(pos);
+                }
+                "enemy" => {
+                    let mut parts = value.split_whitespace();
+                    let pos = parts
+                        .next()
+                        .and_then(parse_position)
+                        .ok_or_else(|| invalid(format!("Invalid enemy '{}'", value)))?;
+                    let kind = match parts.next() {
+                        Some(name) => EnemyKind::from_name(name)
+                            .ok_or_else(|| invalid(format!("Unknown enemy kind '{}'", name)))?,
+                        None => EnemyKind::default(),
+                    };
+                    scenario.enemies.push((pos, kind));
+                }
+                other => return Err(invalid(format!("Unknown header '{}'", other))),
+            }
+        }
+
+        // The marker line ends with its own newline, which is not part of the buffer
+        let text = text.strip_prefix('\n').unwrap_or(text);
+        let mut cursor = None;
+        for (row, line) in text.lines().enumerate() {
+            let line = match line.split_once(CURSOR_MARKER) {
+                Some((before, after)) => {
+                    if cursor.is_some() || after.contains(CURSOR_MARKER) {
+                        return Err(invalid(format!("More than one '{}'", CURSOR_MARKER)));
+                    }
+                    cursor = Some(Position {
+                        row,
+                        col: before.chars().count(),
+                    });
+                    format!("{}{}", before, after)
+                }
+                None => line.to_string(),
+            };
+            scenario.lines.push(line);
+        }
+        while scenario.lines.last().is_some_and(|l| l.trim().is_empty()) {
+            scenario.lines.pop();
+        }
+        if scenario.lines.is_empty() {
+            scenario.lines.push(String::new());
+        }
+        scenario.cursor = cursor.unwrap_or_default();
+
+        let buffer = scenario.buffer();
+        let outside = |pos: &Position| buffer.get_char(pos).is_none() && !buffer.is_empty_line(pos);
+        if let Some(pos) = std::iter::once(&scenario.cursor)
+            .chain(&scenario.expect)
+            .chain(scenario.enemies.iter().map(|(pos, _)| pos))
+            .find(|pos| outside(pos))
+        {
+            return Err(invalid(format!(
+                "Position {}:{} is outside the buffer",
+                pos.row, pos.col
+            )));
+        }
+
+        Ok(scenario)
+    }
+
+    /// Loads a scenario from the specified file path
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the buffer of the scenario.
+    pub fn buffer(&self) -> Buffer {
+        Buffer::from(self.lines.clone())
+    }
+}
+
+/// Returns true if `path` names a scenario file.
+pub fn is_scenario_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|e| e == SCENARIO_EXTENSION)
+}
+
+/// Parses a `row:col` position.
+fn parse_position(text: &str) -> Option<Position> {
+    let (row, col) = text.split_once(':')?;
+    Some(Position {
+        row: row.trim().parse().ok()?,
+        col: col.trim().parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod scenario_tests {
+    use super::*;
+    use crate::domain::motions::MotionFamily;
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario = Scenario::parse(
+            "title: Find the paren\nkeys: f(\nexpect: 1:7\nenemy: 0:2\nenemy: 1:3 armored-find\n\
+             === buffer\nlet a = 1;\nfoo($0bar(x));\n\n",
+        )
+        .unwrap();
+
+        assert_eq!(scenario.title, "Find the paren");
+        assert_eq!(scenario.keys, "f(");
+        assert_eq!(scenario.lines, vec!["let a = 1;", "foo(bar(x));"]);
+        assert_eq!(scenario.cursor, Position { row: 1, col: 4 });
+        assert_eq!(scenario.expect, Some(Position { row: 1, col: 7 }));
+        assert_eq!(
+            scenario.enemies,
+            vec![
+                (Position { row: 0, col: 2 }, EnemyKind::Wanderer),
+                (
+                    Position { row: 1, col: 3 },
+                    EnemyKind::Armored(MotionFamily::Find)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cursor_defaults_to_start() {
+        let scenario = Scenario::parse("=== buffer\nfn main() {}\n").unwrap();
+        assert_eq!(scenario.cursor, Position::default());
+        assert_eq!(scenario.title, "Untitled");
+    }
+
+    #[test]
+    fn test_invalid_scenarios() {
+        for contents in [
+            "keys: w\n",
+            "enemy: 0:1 dragon\n=== buffer\nabc\n",
+            "expect: 3:0\n=== buffer\nabc\n",
+            "speed: 2\n=== buffer\nabc\n",
+            "=== buffer\n$0a$0bc\n",
+        ] {
+            assert!(Scenario::parse(contents).is_err(), "{}", contents);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::domain::motions::{Motion, MotionFamily};
+
+/// Time after a kill within which the next kill extends the combo chain
+pub const COMBO_WINDOW: Duration = Duration::from_secs(3);
+/// Highest combo multiplier
+pub const MAX_COMBO: usize = 5;
+/// Most keystrokes since the previous kill still rewarded as efficient
+pub const EFFICIENT_KEYSTROKES: usize = 3;
+/// Points for an efficient kill
+pub const EFFICIENT_BONUS: usize = 10;
+/// Shortest distance in cells of a single motion rewarded as a long shot
+pub const LONG_SHOT_DISTANCE: usize = 8;
+/// Points for a kill with a find/till motion or a count
+pub const ADVANCED_BONUS: usize = 10;
+
+/// Why points were awarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScoreReason {
+    /// An enemy was destroyed
+    Kill,
+    /// An enemy was destroyed shortly after the previous one
+    Combo,
+    /// The kill took few keystrokes
+    FewKeystrokes,
+    /// The kill was a single motion covering a long distance
+    LongShot,
+    /// The kill used a find/till motion or a count
+    AdvancedMotion,
+}
+
+impl Display for ScoreReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ScoreReason::Kill => "KILL",
+            ScoreReason::Combo => "COMBO",
+            ScoreReason::FewKeystrokes => "EFFICIENT",
+            ScoreReason::LongShot => "LONG SHOT",
+            ScoreReason::AdvancedMotion => "ADVANCED",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Points awarded at once, worth `base * multiplier`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Award {
+    pub base: usize,
+    pub multiplier: usize,
+    pub reason: ScoreReason,
+}
+
+impl Award {
+    pub fn points(&self) -> usize {
+        self.base * self.multiplier
+    }
+}
+
+/// How an enemy was destroyed.
+#[derive(Clone, Copy, Debug)]
+pub struct Kill {
+    /// Points of the enemy's kind
+    pub points: usize,
+    /// Motion that landed the kill, None for a bomb
+    pub motion: Option<(Motion, Option<usize>)>,
+    /// Cells between the cursor before and after the motion
+    pub distance: usize,
+}
+
+/// Tracks combo chains and keystrokes to turn kills into awards.
+#[derive(Debug, Default)]
+pub struct Scoring {
+    /// Kills in the current chain
+    chain: usize,
+    last_kill: Option<Instant>,
+    /// Keystrokes typed since the previous kill
+    keystrokes: usize,
+}
+
+impl Scoring {
+    /// Returns the multiplier the next kill gets if it lands within the combo window.
+    pub fn chain(&self) -> usize {
+        self.chain
+    }
+
+    /// Counts a key typed by the player.
+    pub fn record_keystroke(&mut self) {
+        self.keystrokes += 1;
+    }
+
+    /// Scores a kill made at `now`, `multiplier` applying on top of the combo chain.
+    pub fn score_kill(&mut self, kill: &Kill, multiplier: usize, now: Instant) -> Vec<Award> {
+        let in_combo = self
+            .last_kill
+            .is_some_and(|at| now.duration_since(at) <= COMBO_WINDOW);
+        self.chain = if in_combo { self.chain + 1 } else { 1 };
+        self.last_kill = Some(now);
+        let keystrokes = std::mem::take(&mut self.keystrokes);
+
+        let multiplier = self.chain.min(MAX_COMBO) * multiplier;
+        let award = |base, reason| Award {
+            base,
+            multiplier,
+            reason,
+        };
+
+        let mut awards = vec![award(
+            kill.points,
+            if self.chain > 1 {
+                ScoreReason::Combo
+            } else {
+                ScoreReason::Kill
+            },
+        )];
+
+        // Bonuses only reward kills the player aimed for
+        let Some((motion, count)) = kill.motion else {
+            return awards;
+        };
+        if keystrokes <= EFFICIENT_KEYSTROKES {
+            awards.push(award(EFFICIENT_BONUS, ScoreReason::FewKeystrokes));
+        }
+        if kill.distance >= LONG_SHOT_DISTANCE {
+            awards.push(award(kill.distance, ScoreReason::LongShot));
+        }
+        if motion.kind().family() == MotionFamily::Find || count.is_some_and(|c| c > 1) {
+            awards.push(award(ADVANCED_BONUS, ScoreReason::AdvancedMotion));
+        }
+        awards
+    }
+}
+
+#[cfg(test)]
+mod scoring_tests {
+    use super::*;
+
+    fn kill(motion: Motion, count: Option<usize>, distance: usize) -> Kill {
+        Kill {
+            points: 10,
+            motion: Some((motion, count)),
+            distance,
+        }
+    }
+
+    #[test]
+    fn test_plain_kill() {
+        let mut scoring = Scoring::default();
+        (0..5).for_each(|_| scoring.record_keystroke());
+
+        let awards = scoring.score_kill(&kill(Motion::Right, None, 1), 1, Instant::now());
+        assert_eq!(
+            awards,
+            vec![Award {
+                base: 10,
+                multiplier: 1,
+                reason: ScoreReason::Kill
+            }]
+        );
+    }
+
+    #[test]
+    fn test_combo_and_bonuses() {
+        let mut scoring = Scoring::default();
+        let now = Instant::now();
+        scoring.score_kill(&kill(Motion::Right, None, 1), 1, now);
+
+        scoring.record_keystroke();
+        scoring.record_keystroke();
+        let awards = scoring.score_kill(
+            &kill(Motion::FindNextChar('x'), None, 12),
+            2,
+            now + COMBO_WINDOW,
+        );
+        let reasons: Vec<ScoreReason> = awards.iter().map(|a| a.reason).collect();
+
+        assert_eq!(
+            reasons,
+            vec![
+                ScoreReason::Combo,
+                ScoreReason::FewKeystrokes,
+                ScoreReason::LongShot,
+                ScoreReason::AdvancedMotion
+            ]
+        );
+        // Chain of 2 doubled by the extra multiplier
+        assert!(awards.iter().all(|a| a.multiplier == 4));
+        assert_eq!(awards[2].points(), 48);
+    }
+
+    #[test]
+    fn test_combo_breaks_after_window() {
+        let mut scoring = Scoring::default();
+        let now = Instant::now();
+        scoring.score_kill(&kill(Motion::Right, None, 1), 1, now);
+
+        let late = now + COMBO_WINDOW + Duration::from_millis(1);
+        let awards = scoring.score_kill(&kill(Motion::Right, None, 1), 1, late);
+        assert_eq!(awards[0].reason, ScoreReason::Kill);
+        assert_eq!(scoring.chain(), 1);
+    }
+}
@@ -0,0 +1,1263 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
+    fmt::Display,
+    hash::{BuildHasherDefault, Hasher},
+    ops::Range,
+};
+
+use crate::domain::{
+    Buffer, Direction, Position,
+    motions::{Motion, MotionKind, MotionSet},
+};
+
+/// Default upper bound for the count prefix tried by the solver.
+pub const DEFAULT_MAX_COUNT: usize = 999;
+
+/// A single group of keys typed by the player, e.g. `3w`, `f(` or `;`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// A motion with an optional count prefix
+    Motion {
+        motion: Motion,
+        count: Option<usize>,
+    },
+    /// `;` - repeat the last find/till motion
+    RepeatFind,
+    /// `,` - repeat the last find/till motion in the opposite direction
+    RepeatFindReverse,
+}
+
+impl Step {
+    /// Returns the number of keystrokes needed to type this step.
+    pub fn cost(&self) -> usize {
+        match self {
+            Step::Motion { motion, count } => count_len(*count) + motion.key_len(),
+            Step::RepeatFind | Step::RepeatFindReverse => 1,
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Motion {
+                motion,
+                count: Some(count),
+            } => write!(f, "{}{}", count, motion),
+            Step::Motion {
+                motion,
+                count: None,
+            } => write!(f, "{}", motion),
+            Step::RepeatFind => write!(f, "{}", MotionKind::RepeatFind.key()),
+            Step::RepeatFindReverse => write!(f, "{}", MotionKind::RepeatFindReverse.key()),
+        }
+    }
+}
+
+/// The cheapest sequence of steps found by the solver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    /// The target position the route ends on
+    pub target: Position,
+    /// Steps to type, in order
+    pub steps: Vec<Step>,
+    /// Cursor position after each step
+    pub path: Vec<Position>,
+    /// Total number of keystrokes
+    pub cost: usize,
+}
+
+impl Route {
+    /// Returns the full key sequence of the route, e.g. `2f(;`.
+    pub fn keys(&self) -> String {
+        self.steps.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Returns the motion of the last step, the one landing on the target.
+    pub fn last_motion(&self) -> Option<Motion> {
+        let mut last_find = None;
+        let mut last = None;
+        for step in &self.steps {
+            last = match step {
+                Step::Motion { motion, .. } => {
+                    if motion.is_find_till() {
+                        last_find = Some(*motion);
+                    }
+                    Some(*motion)
+                }
+                Step::RepeatFind => last_find,
+                Step::RepeatFindReverse => last_find.and_then(|m| m.reverse_find_till()),
+            };
+        }
+        last
+    }
+}
+
+/// Search state: the cursor position plus the find/till motion `;` and `,` would repeat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct State {
+    pos: Position,
+    last_find: Option<Motion>,
+}
+
+/// Multiplicative hasher for the solver's small keys, much cheaper than the default SipHash.
+#[derive(Default)]
+struct FastHasher(u64);
+
+impl Hasher for FastHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(b as u64);
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+
+type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<FastHasher>>;
+type FastSet<K> = HashSet<K, BuildHasherDefault<FastHasher>>;
+
+/// A visited search state, linked to the state it was reached from.
+struct Node {
+    state: State,
+    cost: usize,
+    parent: Option<(usize, Step)>,
+    settled: bool,
+    /// Whether the route ends here, on a target landed on with an accepted motion
+    target: bool,
+}
+
+/// Decides whether landing on a target with the given motion ends the route,
+/// None meaning the route starts on it.
+pub type AcceptLanding<'a> = &'a dyn Fn(Position, Option<Motion>) -> bool;
+
+/// Successors of a node that share the same cost: one motion kind typed with a
+/// count prefix of the same length, e.g. every `f{c}` or every `2w` to `9w`.
+///
+/// Batches are only expanded once popped from the queue, so expensive steps
+/// (long counts, every find target) are never generated if a cheaper route exists.
+#[derive(Clone, Copy)]
+struct Batch {
+    from: usize,
+    kind: MotionKind,
+    counts: (usize, usize),
+}
+
+/// Entries of the search queue, ordered by cost with nodes ahead of batches.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Entry {
+    Node(usize),
+    Batch(usize),
+}
+
+/// Regions around the targets used to prune states that cannot reach them cheaply.
+struct TargetArea<'a> {
+    targets: &'a HashSet<Position>,
+    /// Rows from which a single plain motion could land on a target
+    near_rows: FastSet<usize>,
+    /// Rows from which two keys of plain motions could land on a target
+    reach_rows: FastSet<usize>,
+    /// Buffer spans from which a single find/till could land on a target
+    find_spans: Vec<(Position, Position)>,
+    /// First and last position of the rows and spans above, in buffer order
+    window: (Position, Position),
+    /// Indices of each character's occurrences within two repeats of the window
+    near_occurrences: Vec<(char, Range<usize>)>,
+}
+
+impl TargetArea<'_> {
+    fn in_find_span(&self, pos: Position) -> bool {
+        self.find_spans.iter().any(|(lo, hi)| {
+            (lo.row, lo.col) <= (pos.row, pos.col) && (pos.row, pos.col) <= (hi.row, hi.col)
+        })
+    }
+}
+
+/// Bookkeeping of a single search.
+struct Search<'a> {
+    area: &'a TargetArea<'a>,
+    accept: AcceptLanding<'a>,
+    nodes: Vec<Node>,
+    index: FastMap<State, usize>,
+    batches: Vec<Batch>,
+    queue: BinaryHeap<Reverse<(usize, Entry)>>,
+    /// Positions whose motions were already expanded from a cheaper or equal state
+    expanded: FastSet<Position>,
+    /// Cost limit of the search, tightened to the cheapest target seen so far
+    bound: usize,
+    /// Whether anything was skipped because of the bound
+    pruned: bool,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        start: Position,
+        area: &'a TargetArea<'a>,
+        accept: AcceptLanding<'a>,
+        bound: usize,
+    ) -> Self {
+        let mut search = Self {
+            area,
+            accept,
+            nodes: vec![],
+            index: FastMap::default(),
+            batches: vec![],
+            queue: BinaryHeap::new(),
+            expanded: FastSet::default(),
+            bound,
+            pruned: false,
+        };
+        let state = State {
+            pos: start,
+            last_find: None,
+        };
+        search.relax(None, state, 0);
+        search
+    }
+
+    /// Records `state` as reachable with `cost`, keeping the cheapest way to get there.
+    fn relax(&mut self, parent: Option<(usize, Step)>, state: State, cost: usize) {
+        // Only a target itself is worth recording right below the bound
+        let is_target = self.area.targets.contains(&state.pos)
+            && (self.accept)(state.pos, landing_motion(parent, state));
+        if cost >= self.bound || (cost + 1 == self.bound && !is_target) {
+            self.pruned = true;
+            return;
+        }
+
+        // Landing on a target ends the route, so it is kept apart from the same
+        // state passing through with a motion that was not accepted
+        if is_target {
+            self.nodes.push(Node {
+                state,
+                cost,
+                parent,
+                settled: false,
+                target: true,
+            });
+            self.bound = cost;
+            let entry = Entry::Node(self.nodes.len() - 1);
+            self.queue.push(Reverse((cost, entry)));
+            return;
+        }
+
+        let idx = match self.index.get(&state) {
+            Some(&i) if self.nodes[i].settled || self.nodes[i].cost <= cost => return,
+            Some(&i) => {
+                self.nodes[i].cost = cost;
+                self.nodes[i].parent = parent;
+                i
+            }
+            None => {
+                self.nodes.push(Node {
+                    state,
+                    cost,
+                    parent,
+                    settled: false,
+                    target: false,
+                });
+                self.index.insert(state, self.nodes.len() - 1);
+                self.nodes.len() - 1
+            }
+        };
+
+        self.queue.push(Reverse((cost, Entry::Node(idx))));
+    }
+
+    fn push_batch(&mut self, batch: Batch, cost: usize) {
+        if cost < self.bound {
+            self.batches.push(batch);
+            let entry = Entry::Batch(self.batches.len() - 1);
+            self.queue.push(Reverse((cost, entry)));
+        } else {
+            self.pruned = true;
+        }
+    }
+}
+
+/// Finds the minimum-keystroke sequence of motions between two positions.
+///
+/// Runs Dijkstra over the results of `Motion::apply`, where each edge costs the
+/// number of keys typed. Single motion steps are memoised, so repeated queries
+/// against the same buffer get cheaper over time; call `clear` when the buffer changes.
+///
+/// The solver mirrors the input rules of the game: counts go up to `max_count`,
+/// `;` and `,` take no count and only repeat a find/till motion typed directly before.
+/// The column memory of vertical motions is not modelled.
+///
+/// A route of counted `j`/`k` motions to the row of a target bounds the search,
+/// and close to that bound only states near the targets are generated, so far
+/// targets on large files stay cheap to solve.
+pub struct MotionSolver {
+    motions: MotionSet,
+    max_count: usize,
+    /// Memoised results of applying a motion once at a position.
+    transitions: FastMap<(Position, Motion), Position>,
+    /// Positions of every character in the buffer, in buffer order.
+    occurrences: Option<BTreeMap<char, Vec<Position>>>,
+    /// Longest run of blank lines in the buffer, which a word motion may skip at once.
+    blank_run: Option<usize>,
+}
+
+impl Default for MotionSolver {
+    fn default() -> Self {
+        Self::new(MotionSet::all(), DEFAULT_MAX_COUNT)
+    }
+}
+
+impl MotionSolver {
+    pub fn new(motions: MotionSet, max_count: usize) -> Self {
+        Self {
+            motions,
+            max_count: max_count.max(1),
+            transitions: FastMap::default(),
+            occurrences: None,
+            blank_run: None,
+        }
+    }
+
+    /// Returns the motions the solver is allowed to use.
+    pub fn motions(&self) -> &MotionSet {
+        &self.motions
+    }
+
+    /// Forgets all memoised results, must be called when the buffer changes.
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+        self.occurrences = None;
+        self.blank_run = None;
+    }
+
+    /// Returns the cheapest route from `start` to `target`, if reachable.
+    pub fn solve(&mut self, buffer: &Buffer, start: Position, target: Position) -> Option<Route> {
+        self.solve_nearest(buffer, start, &HashSet::from([target]))
+    }
+
+    /// Returns the cheapest route from `start` to whichever of `targets` is cheapest to reach.
+    ///
+    /// Returns None if none is reachable.
+    pub fn solve_nearest(
+        &mut self,
+        buffer: &Buffer,
+        start: Position,
+        targets: &HashSet<Position>,
+    ) -> Option<Route> {
+        self.solve_nearest_where(buffer, start, targets, &|_, _| true)
+    }
+
+    /// Like `solve_nearest`, but a route only ends on a target if `accept` takes
+    /// the motion landing there, e.g. to skip enemies it would bounce off.
+    ///
+    /// Targets landed on with other motions are passed through like any position.
+    pub fn solve_nearest_where(
+        &mut self,
+        buffer: &Buffer,
+        start: Position,
+        targets: &HashSet<Position>,
+        accept: AcceptLanding,
+    ) -> Option<Route> {
+        if buffer.is_empty() || targets.is_empty() {
+            return None;
+        }
+
+        let area = self.target_area(buffer, targets);
+        let direct = targets
+            .iter()
+            .filter_map(|&target| self.direct_route(buffer, start, target, accept))
+            .min_by_key(|route| route.cost);
+
+        // Deepen the cost limit until a route is found, so that expensive steps
+        // are only ever generated when nothing cheaper exists, up to the direct route
+        let max_limit = direct.as_ref().map_or(usize::MAX, |route| route.cost);
+        for limit in 1..max_limit {
+            let mut search = Search::new(start, &area, accept, limit + 1);
+            if let Some(route) = self.search(buffer, &mut search) {
+                return Some(route);
+            }
+            if !search.pruned {
+                break; // Every reachable state was visited
+            }
+        }
+
+        direct
+    }
+
+    /// Builds a route of counted `j`/`k` motions to the row of `target`, then the
+    /// cheapest route along that row, or counted `l`/`h` motions if already on it.
+    ///
+    /// Rarely the cheapest, but quick to find, so it bounds the search on large
+    /// files. Returns None if the motions are not allowed, miss the target or land
+    /// on it with a motion `accept` does not take.
+    fn direct_route(
+        &mut self,
+        buffer: &Buffer,
+        start: Position,
+        target: Position,
+        accept: AcceptLanding,
+    ) -> Option<Route> {
+        let (motion, distance) = match target.row.cmp(&start.row) {
+            Ordering::Greater => (Motion::Down, target.row - start.row),
+            Ordering::Less => (Motion::Up, start.row - target.row),
+            Ordering::Equal if target.col >= start.col => (Motion::Right, target.col - start.col),
+            Ordering::Equal => (Motion::Left, start.col - target.col),
+        };
+        if distance > 0 && !self.motions.contains(motion.kind()) {
+            return None;
+        }
+
+        let mut route = Route {
+            target,
+            steps: vec![],
+            path: vec![],
+            cost: 0,
+        };
+        let mut pos = start;
+        let mut distance = distance;
+        while distance > 0 {
+            let count = distance.min(self.max_count);
+            let step = Step::Motion {
+                motion,
+                count: (count > 1).then_some(count),
+            };
+            pos = motion.apply(buffer, pos, count);
+            route.steps.push(step);
+            route.path.push(pos);
+            route.cost += step.cost();
+            distance -= count;
+        }
+
+        if pos.row != start.row && pos != target {
+            let targets = HashSet::from([target]);
+            let rest = self.solve_nearest_where(buffer, pos, &targets, accept)?;
+            route.steps.extend(rest.steps);
+            route.path.extend(rest.path);
+            route.cost += rest.cost;
+        }
+        (route.path.last().copied().unwrap_or(start) == target
+            && accept(target, route.last_motion()))
+        .then_some(route)
+    }
+
+    /// Runs Dijkstra until a target is settled or the queue runs out.
+    fn search(&mut self, buffer: &Buffer, search: &mut Search) -> Option<Route> {
+        while let Some(Reverse((cost, entry))) = search.queue.pop() {
+            match entry {
+                Entry::Node(idx) => {
+                    if search.nodes[idx].settled {
+                        continue;
+                    }
+                    search.nodes[idx].settled = true;
+
+                    if search.nodes[idx].target {
+                        return Some(build_route(&search.nodes, idx));
+                    }
+                    self.expand(buffer, search, idx);
+                }
+                Entry::Batch(idx) => {
+                    if cost < search.bound {
+                        let batch = search.batches[idx];
+                        self.expand_batch(buffer, search, batch, cost);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Queues the successors of a settled node.
+    fn expand(&mut self, buffer: &Buffer, search: &mut Search, idx: usize) {
+        let Node { state, cost, .. } = search.nodes[idx];
+
+        // Only `;` and `,` depend on the last find, the rest was already explored
+        let repeats_only = !search.expanded.insert(state.pos);
+
+        for kind in MotionKind::ALL {
+            if !self.motions.contains(kind) {
+                continue;
+            }
+
+            match kind {
+                MotionKind::RepeatFind | MotionKind::RepeatFindReverse => {
+                    let Some(last) = state.last_find else {
+                        continue;
+                    };
+                    let (motion, step) = if kind == MotionKind::RepeatFind {
+                        (last, Step::RepeatFind)
+                    } else {
+                        let reversed = last.reverse_find_till().expect("Last is find/till");
+                        (reversed, Step::RepeatFindReverse)
+                    };
+
+                    let pos = self.step(buffer, state.pos, motion);
+                    let next = State {
+                        pos,
+                        last_find: Some(last),
+                    };
+                    if pos != state.pos && self.worth_visiting(buffer, search, next, cost + 1) {
+                        search.relax(Some((idx, step)), next, cost + 1);
+                    }
+                }
+                _ if repeats_only => {}
+                kind => {
+                    // One batch per count length: 1, 2-9, 10-99...
+                    let key_len = if kind.needs_target() { 2 } else { 1 };
+                    let mut lo = 1;
+                    while lo <= self.max_count {
+                        let hi = if lo == 1 {
+                            1
+                        } else {
+                            (10usize.pow(count_len(Some(lo)) as u32) - 1).min(self.max_count)
+                        };
+                        let batch = Batch {
+                            from: idx,
+                            kind,
+                            counts: (lo, hi),
+                        };
+                        search.push_batch(batch, cost + key_len + count_len(Some(lo)));
+                        lo = hi + 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates every successor of a batch.
+    fn expand_batch(&mut self, buffer: &Buffer, search: &mut Search, batch: Batch, cost: usize) {
+        // Close to the bound, successors are only kept near the targets
+        let near_bound = cost + 3 >= search.bound;
+        if near_bound {
+            search.pruned = true;
+        }
+
+        if batch.kind.needs_target() && near_bound {
+            self.expand_finds_near(buffer, search, batch, cost);
+        } else if batch.kind.needs_target() {
+            let motions: Vec<Motion> = self
+                .occurrences(buffer)
+                .keys()
+                .filter_map(|&c| Motion::with_target(batch.kind, c))
+                .collect();
+            for motion in motions {
+                self.expand_counts(buffer, search, batch, motion, cost);
+            }
+        } else if let Some(motion) = Motion::from_kind(batch.kind) {
+            self.expand_counts(buffer, search, batch, motion, cost);
+        }
+    }
+
+    /// Generates the successors of a batch typing `motion`, stepping through its counts.
+    fn expand_counts(
+        &mut self,
+        buffer: &Buffer,
+        search: &mut Search,
+        batch: Batch,
+        motion: Motion,
+        cost: usize,
+    ) {
+        let near_bound = cost + 3 >= search.bound;
+        // Rows a single plain motion may cross, to stop once the targets are out of reach
+        let rows_per_step = match motion {
+            Motion::Down | Motion::Up | Motion::Left | Motion::Right => Some(1),
+            Motion::WordStart | Motion::WordEnd | Motion::WordBackward => {
+                Some(1 + self.blank_run(buffer))
+            }
+            _ => None,
+        };
+        let forward = matches!(
+            motion,
+            Motion::Down | Motion::Right | Motion::WordStart | Motion::WordEnd
+        );
+        let last_find = motion.is_find_till().then_some(motion);
+        let (lo, hi) = batch.counts;
+        let mut pos = search.nodes[batch.from].state.pos;
+
+        for n in 1..=hi {
+            let next = self.step(buffer, pos, motion);
+            if next == pos {
+                break; // Any larger count lands on the same position
+            }
+            pos = next;
+
+            if near_bound
+                && let Some(rows_per_step) = rows_per_step
+                && !can_reach(search.area.window, pos, forward, (hi - n) * rows_per_step)
+            {
+                break;
+            }
+
+            let next = State { pos, last_find };
+            if n >= lo && self.worth_visiting(buffer, search, next, cost) {
+                let step = Step::Motion {
+                    motion,
+                    count: (n > 1).then_some(n),
+                };
+                search.relax(Some((batch.from, step)), next, cost);
+            }
+        }
+    }
+
+    /// Generates the successors of a find/till batch close to the bound.
+    ///
+    /// Only landings within two repeats of the target window can be kept, so the
+    /// counts landing there are looked up in the occurrence index instead of
+    /// stepping through every character and count.
+    fn expand_finds_near(
+        &mut self,
+        buffer: &Buffer,
+        search: &mut Search,
+        batch: Batch,
+        cost: usize,
+    ) {
+        let start = search.nodes[batch.from].state.pos;
+        let (lo, hi) = batch.counts;
+        let mut landings = vec![];
+        let mut tills = vec![];
+
+        let occurrences = self.occurrences(buffer);
+        for (c, near) in &search.area.near_occurrences {
+            let occurrences = &occurrences[c];
+            // Whether at least `k` occurrences come before the start, or at it
+            let after_at_least = |k| occurs_before(occurrences, k, start, false);
+            let until_at_least = |k| occurs_before(occurrences, k, start, true);
+            let after =
+                || occurrences.partition_point(|o| (o.row, o.col) <= (start.row, start.col));
+            let until = || occurrences.partition_point(|o| (o.row, o.col) < (start.row, start.col));
+
+            // Count `n` lands on the `n`th occurrence after or before the start, checked
+            // against the near occurrences before looking the start up
+            match batch.kind {
+                MotionKind::FindNextChar
+                    if after_at_least((near.start + 1).saturating_sub(hi))
+                        && !after_at_least(near.end + 1 - lo) =>
+                {
+                    let after = after();
+                    let first = near.start.max(after + lo - 1);
+                    let last = near.end.min(after + hi);
+                    for (i, &pos) in occurrences.iter().enumerate().take(last).skip(first) {
+                        landings.push((Motion::FindNextChar(*c), i + 1 - after, pos));
+                    }
+                }
+                MotionKind::FindPrevChar
+                    if until_at_least(near.start + lo) && !until_at_least(near.end + hi) =>
+                {
+                    let until = until();
+                    let first = near.start.max(until.saturating_sub(hi));
+                    let last = near.end.min(until + 1 - lo);
+                    for (i, &pos) in occurrences.iter().enumerate().take(last).skip(first) {
+                        landings.push((Motion::FindPrevChar(*c), until - i, pos));
+                    }
+                }
+                // Tills stop next to the first occurrence, and larger counts stay there
+                MotionKind::TillNextChar
+                    if after_at_least(near.start) && !after_at_least(near.end) =>
+                {
+                    tills.push(Motion::TillNextChar(*c));
+                }
+                MotionKind::TillPrevChar
+                    if until_at_least(near.start + 1) && !until_at_least(near.end + 1) =>
+                {
+                    tills.push(Motion::TillPrevChar(*c));
+                }
+                _ => {}
+            }
+        }
+
+        for (motion, n, pos) in landings {
+            let next = State {
+                pos,
+                last_find: Some(motion),
+            };
+            if self.worth_visiting(buffer, search, next, cost) {
+                let step = Step::Motion {
+                    motion,
+                    count: (n > 1).then_some(n),
+                };
+                search.relax(Some((batch.from, step)), next, cost);
+            }
+        }
+        for motion in tills {
+            self.expand_counts(buffer, search, batch, motion, cost);
+        }
+    }
+
+    /// Returns true if `state` reached with `cost` could still lead to a cheaper route.
+    ///
+    /// Close to the bound, states are only kept if they are near enough to a target
+    /// to reach it with the keys left.
+    fn worth_visiting(
+        &mut self,
+        buffer: &Buffer,
+        search: &mut Search,
+        state: State,
+        cost: usize,
+    ) -> bool {
+        let area = search.area;
+        let keys_left = search.bound.saturating_sub(cost + 1);
+
+        let worth = if area.targets.contains(&state.pos) {
+            true // Relaxing checks the bound itself
+        } else {
+            match keys_left {
+                0 => false,
+                1 => {
+                    area.near_rows.contains(&state.pos.row)
+                        || self
+                            .repeats(buffer, state)
+                            .iter()
+                            .any(|pos| area.targets.contains(pos))
+                }
+                2 => {
+                    area.reach_rows.contains(&state.pos.row)
+                        || area.in_find_span(state.pos)
+                        || self.repeats(buffer, state).into_iter().any(|pos| {
+                            area.targets.contains(&pos)
+                                || area.near_rows.contains(&pos.row)
+                                || self
+                                    .repeats(buffer, State { pos, ..state })
+                                    .iter()
+                                    .any(|pos| area.targets.contains(pos))
+                        })
+                }
+                _ => true,
+            }
+        };
+
+        search.pruned |= !worth;
+        worth
+    }
+
+    /// Returns the positions `;` and `,` lead to from `state`.
+    fn repeats(&mut self, buffer: &Buffer, state: State) -> Vec<Position> {
+        let mut positions = vec![];
+        if let Some(last) = state.last_find {
+            let repeats = [
+                (MotionKind::RepeatFind, Some(last)),
+                (MotionKind::RepeatFindReverse, last.reverse_find_till()),
+            ];
+            for (kind, motion) in repeats {
+                if let Some(motion) = motion
+                    && self.motions.contains(kind)
+                {
+                    positions.push(self.step(buffer, state.pos, motion));
+                }
+            }
+        }
+        positions
+    }
+
+    /// Computes the regions around `targets` used to prune the search.
+    fn target_area<'a>(
+        &mut self,
+        buffer: &Buffer,
+        targets: &'a HashSet<Position>,
+    ) -> TargetArea<'a> {
+        let is_blank = |row: usize| buffer.get_line(row).is_some_and(|l| l.trim().is_empty());
+
+        // Rows around the target with at most `skip` non-blank rows in between,
+        // since word motions pass over blank lines
+        let rows_around = |row: usize, skip: usize| {
+            let mut rows = vec![row];
+            let mut passed = 0;
+            let mut r = row;
+            while r > 0 && passed <= skip {
+                r -= 1;
+                rows.push(r);
+                if !is_blank(r) {
+                    passed += 1;
+                }
+            }
+            passed = 0;
+            r = row;
+            while r + 1 < buffer.rows() && passed <= skip {
+                r += 1;
+                rows.push(r);
+                if !is_blank(r) {
+                    passed += 1;
+                }
+            }
+            rows
+        };
+
+        let mut near_rows = FastSet::default();
+        let mut reach_rows = FastSet::default();
+        let mut find_spans = vec![];
+        let end = Position {
+            row: usize::MAX,
+            col: usize::MAX,
+        };
+
+        for &target in targets {
+            near_rows.extend(rows_around(target.row, 0));
+            reach_rows.extend(rows_around(target.row, 9));
+
+            // f/F land on the target's own character, t/T on the ones next to it
+            let mut after = target;
+            let mut before = target;
+            let kinds = [
+                (MotionKind::FindNextChar, Some(target)),
+                (MotionKind::FindPrevChar, Some(target)),
+                (
+                    MotionKind::TillNextChar,
+                    after.step_char(buffer, Direction::Forward).then_some(after),
+                ),
+                (
+                    MotionKind::TillPrevChar,
+                    before
+                        .step_char(buffer, Direction::Backward)
+                        .then_some(before),
+                ),
+            ];
+
+            for (kind, anchor) in kinds {
+                let Some(anchor) = anchor else {
+                    continue;
+                };
+                let Some(c) = buffer.get_char(&anchor) else {
+                    continue;
+                };
+                if !self.motions.contains(kind) {
+                    continue;
+                }
+
+                let occurrences = &self.occurrences(buffer)[&c];
+                let at = occurrences.partition_point(|o| (o.row, o.col) < (anchor.row, anchor.col));
+                let span = match kind {
+                    MotionKind::FindNextChar | MotionKind::TillNextChar => {
+                        let lo = at
+                            .checked_sub(1)
+                            .map_or(Position::default(), |i| occurrences[i]);
+                        (lo, anchor)
+                    }
+                    _ => (anchor, occurrences.get(at + 1).copied().unwrap_or(end)),
+                };
+                find_spans.push(span);
+            }
+        }
+
+        let rows = reach_rows.iter().chain(&near_rows);
+        let lo = rows.clone().min().map(|&row| Position { row, col: 0 });
+        let hi = rows.max().map(|&row| Position {
+            row,
+            col: usize::MAX,
+        });
+        let spans = find_spans.iter();
+        let window = (
+            lo.into_iter()
+                .chain(spans.clone().map(|(lo, _)| *lo))
+                .min_by_key(|p| (p.row, p.col))
+                .unwrap_or_default(),
+            hi.into_iter()
+                .chain(spans.map(|(_, hi)| *hi))
+                .max_by_key(|p| (p.row, p.col))
+                .unwrap_or_default(),
+        );
+
+        // Widened by a row, as tills land next to the occurrences
+        let lo = (window.0.row.saturating_sub(1), 0);
+        let hi = (window.1.row.saturating_add(1), usize::MAX);
+        let near_occurrences = self
+            .occurrences(buffer)
+            .iter()
+            .filter_map(|(&c, occurrences)| {
+                let first = occurrences.partition_point(|o| (o.row, o.col) < lo);
+                let last = occurrences.partition_point(|o| (o.row, o.col) <= hi);
+                (first < last).then(|| {
+                    (
+                        c,
+                        first.saturating_sub(2)..(last + 2).min(occurrences.len()),
+                    )
+                })
+            })
+            .collect();
+
+        TargetArea {
+            targets,
+            near_rows,
+            reach_rows,
+            find_spans,
+            window,
+            near_occurrences,
+        }
+    }
+
+    /// Applies `motion` once at `pos`, using the memoised result when available.
+    fn step(&mut self, buffer: &Buffer, pos: Position, motion: Motion) -> Position {
+        if motion.is_find_till() {
+            return self.find_step(buffer, pos, motion);
+        }
+
+        *self
+            .transitions
+            .entry((pos, motion))
+            .or_insert_with(|| motion.apply(buffer, pos, 1))
+    }
+
+    /// Applies a find/till motion once at `pos`.
+    ///
+    /// Equivalent to `Motion::apply`, but looks the target character up in the
+    /// occurrence index instead of scanning the buffer.
+    fn find_step(&mut self, buffer: &Buffer, pos: Position, motion: Motion) -> Position {
+        let (target, direction, till) = match motion {
+            Motion::FindNextChar(c) => (c, Direction::Forward, false),
+            Motion::FindPrevChar(c) => (c, Direction::Backward, false),
+            Motion::TillNextChar(c) => (c, Direction::Forward, true),
+            Motion::TillPrevChar(c) => (c, Direction::Backward, true),
+            _ => unreachable!("Motion is find/till"),
+        };
+
+        let Some(occurrences) = self.occurrences(buffer).get(&target) else {
+            return pos;
+        };
+
+        // Occurrences are sorted in buffer order
+        let found = match direction {
+            Direction::Forward => {
+                let after = occurrences.partition_point(|o| (o.row, o.col) <= (pos.row, pos.col));
+                occurrences.get(after)
+            }
+            Direction::Backward => {
+                let before = occurrences.partition_point(|o| (o.row, o.col) < (pos.row, pos.col));
+                before.checked_sub(1).map(|i| &occurrences[i])
+            }
+        };
+
+        match found {
+            Some(&found) if till => {
+                let mut before = found;
+                if before.step_char(buffer, direction.opposite()) {
+                    before
+                } else {
+                    pos
+                }
+            }
+            Some(&found) => found,
+            None => pos,
+        }
+    }
+
+    fn blank_run(&mut self, buffer: &Buffer) -> usize {
+        *self.blank_run.get_or_insert_with(|| {
+            let mut longest = 0;
+            let mut run = 0;
+            for line in buffer.lines() {
+                run = if line.trim().is_empty() { run + 1 } else { 0 };
+                longest = longest.max(run);
+            }
+            longest
+        })
+    }
+
+    fn occurrences(&mut self, buffer: &Buffer) -> &BTreeMap<char, Vec<Position>> {
+        self.occurrences.get_or_insert_with(|| {
+            let mut occurrences: BTreeMap<char, Vec<Position>> = BTreeMap::new();
+            for row in 0..buffer.rows() {
+                if let Some(line) = buffer.get_line(row) {
+                    for (col, c) in line.chars().enumerate() {
+                        occurrences
+                            .entry(c)
+                            .or_default()
+                            .push(Position { row, col });
+                    }
+                }
+            }
+            occurrences
+        })
+    }
+}
+
+/// Returns the motion of the step landing on `state`, None for the start.
+fn landing_motion(parent: Option<(usize, Step)>, state: State) -> Option<Motion> {
+    match parent?.1 {
+        Step::Motion { motion, .. } => Some(motion),
+        Step::RepeatFind => state.last_find,
+        Step::RepeatFindReverse => state.last_find.and_then(|m| m.reverse_find_till()),
+    }
+}
+
+/// Walks the parent links back from `idx` to build the route.
+fn build_route(nodes: &[Node], idx: usize) -> Route {
+    let target = nodes[idx].state.pos;
+    let cost = nodes[idx].cost;
+    let mut steps = vec![];
+    let mut path = vec![];
+
+    let mut current = idx;
+    while let Some((parent, step)) = nodes[current].parent {
+        steps.push(step);
+        path.push(nodes[current].state.pos);
+        current = parent;
+    }
+    steps.reverse();
+    path.reverse();
+
+    Route {
+        target,
+        steps,
+        path,
+        cost,
+    }
+}
+
+/// Returns true if a motion moving `forward` from `pos` could still land in `window`
+/// within `rows` more rows.
+fn can_reach(window: (Position, Position), pos: Position, forward: bool, rows: usize) -> bool {
+    let (lo, hi) = window;
+    if forward {
+        (pos.row, pos.col) <= (hi.row, hi.col) && lo.row.saturating_sub(pos.row) <= rows
+    } else {
+        (lo.row, lo.col) <= (pos.row, pos.col) && pos.row.saturating_sub(hi.row) <= rows
+    }
+}
+
+/// Returns true if at least `k` of the sorted `occurrences` come before `pos`, or at it
+/// unless `strict`.
+fn occurs_before(occurrences: &[Position], k: usize, pos: Position, strict: bool) -> bool {
+    let Some(i) = k.checked_sub(1) else {
+        return true;
+    };
+    occurrences.get(i).is_some_and(|o| {
+        if strict {
+            (o.row, o.col) < (pos.row, pos.col)
+        } else {
+            (o.row, o.col) <= (pos.row, pos.col)
+        }
+    })
+}
+
+/// Returns the number of keys needed to type a count prefix.
+fn count_len(count: Option<usize>) -> usize {
+    match count {
+        Some(n) if n > 1 => n.to_string().len(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+
+    fn buffer() -> Buffer {
+        vec![
+            String::from("fn main() {"),
+            String::from("    let value = compute(data, 42);"),
+            String::from(""),
+            String::from("    println!(\"{}\", value);"),
+            String::from("}"),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_solve_start_is_target() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let start = Position { row: 0, col: 0 };
+
+        let route = solver.solve(&buffer, start, start).unwrap();
+        assert_eq!(route.cost, 0);
+        assert!(route.steps.is_empty());
+    }
+
+    #[test]
+    fn test_solve_single_key() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let start = Position { row: 0, col: 0 };
+
+        let route = solver
+            .solve(&buffer, start, Position { row: 0, col: 3 })
+            .unwrap();
+        assert_eq!(route.cost, 1);
+        assert_eq!(route.keys(), "w");
+    }
+
+    #[test]
+    fn test_solve_prefers_find_over_steps() {
+        let buffer = buffer();
+        let motions = MotionSet::only(&[MotionKind::Right, MotionKind::FindNextChar]);
+        let mut solver = MotionSolver::new(motions, DEFAULT_MAX_COUNT);
+        let start = Position { row: 1, col: 4 };
+        let target = Position { row: 1, col: 28 }; // ','
+
+        let route = solver.solve(&buffer, start, target).unwrap();
+        assert_eq!(route.keys(), "f,");
+        assert_eq!(route.path, vec![target]);
+    }
+
+    #[test]
+    fn test_solve_uses_counts() {
+        let buffer = buffer();
+        let motions = MotionSet::only(&[MotionKind::Right]);
+        let mut solver = MotionSolver::new(motions, DEFAULT_MAX_COUNT);
+        let start = Position { row: 1, col: 0 };
+
+        let route = solver
+            .solve(&buffer, start, Position { row: 1, col: 12 })
+            .unwrap();
+        assert_eq!(route.keys(), "12l");
+        assert_eq!(route.cost, 3);
+    }
+
+    #[test]
+    fn test_solve_uses_repeat() {
+        let buffer: Buffer = vec![String::from("a.a.a.a")].into();
+        let motions = MotionSet::only(&[MotionKind::FindNextChar, MotionKind::RepeatFind]);
+        let mut solver = MotionSolver::new(motions, 1);
+        let start = Position { row: 0, col: 0 };
+
+        let route = solver
+            .solve(&buffer, start, Position { row: 0, col: 5 })
+            .unwrap();
+        assert_eq!(route.keys(), "f.;;");
+        assert_eq!(route.cost, 4);
+    }
+
+    #[test]
+    fn test_solve_unreachable() {
+        let buffer = buffer();
+        let motions = MotionSet::only(&[MotionKind::Right]);
+        let mut solver = MotionSolver::new(motions, DEFAULT_MAX_COUNT);
+
+        let route = solver.solve(
+            &buffer,
+            Position { row: 1, col: 5 },
+            Position { row: 0, col: 0 },
+        );
+        assert!(route.is_none());
+    }
+
+    #[test]
+    fn test_solve_nearest_by_keystrokes() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let start = Position { row: 1, col: 4 };
+        // 'p' of println is two rows down but needs three keys, 'v' of value is a single `w`
+        let targets = HashSet::from([Position { row: 3, col: 4 }, Position { row: 1, col: 8 }]);
+
+        let route = solver.solve_nearest(&buffer, start, &targets).unwrap();
+        assert_eq!(route.target, Position { row: 1, col: 8 });
+        assert_eq!(route.cost, 1);
+    }
+
+    #[test]
+    fn test_solve_nearest_where_passes_rejected_landings() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let start = Position { row: 1, col: 4 };
+        let targets = HashSet::from([Position { row: 3, col: 4 }, Position { row: 1, col: 8 }]);
+        let accept = |_, motion: Option<Motion>| motion != Some(Motion::WordStart);
+
+        // `w` to 'v' does not count, but `fv` or `4l` still beat going down to 'p'
+        let route = solver
+            .solve_nearest_where(&buffer, start, &targets, &accept)
+            .unwrap();
+        assert_eq!(route.target, Position { row: 1, col: 8 });
+        assert_eq!(route.cost, 2);
+        assert_ne!(route.last_motion(), Some(Motion::WordStart));
+    }
+
+    #[test]
+    fn test_solve_route_replays() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let start = Position { row: 0, col: 0 };
+        let target = Position { row: 3, col: 20 };
+
+        let route = solver.solve(&buffer, start, target).unwrap();
+
+        // Replaying the steps must land on the target
+        let mut pos = start;
+        let mut last_find = None;
+        for step in &route.steps {
+            pos = match step {
+                Step::Motion { motion, count } => {
+                    if motion.is_find_till() {
+                        last_find = Some(*motion);
+                    }
+                    motion.apply(&buffer, pos, count.unwrap_or(1))
+                }
+                Step::RepeatFind => last_find.unwrap().apply(&buffer, pos, 1),
+                Step::RepeatFindReverse => last_find
+                    .unwrap()
+                    .reverse_find_till()
+                    .unwrap()
+                    .apply(&buffer, pos, 1),
+            };
+        }
+        assert_eq!(pos, target);
+        assert_eq!(
+            route.cost,
+            route.steps.iter().map(|s| s.cost()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_find_step_matches_apply() {
+        let buffer = buffer();
+        let mut solver = MotionSolver::default();
+        let chars: Vec<char> = solver.occurrences(&buffer).keys().copied().collect();
+
+        for row in 0..buffer.rows() {
+            for col in 0..buffer.get_line_len(row).max(1) {
+                let pos = Position { row, col };
+                for &c in &chars {
+                    for kind in [
+                        MotionKind::FindNextChar,
+                        MotionKind::FindPrevChar,
+                        MotionKind::TillNextChar,
+                        MotionKind::TillPrevChar,
+                    ] {
+                        let motion = Motion::with_target(kind, c).unwrap();
+                        assert_eq!(
+                            solver.find_step(&buffer, pos, motion),
+                            motion.apply(&buffer, pos, 1),
+                            "{} at {:?}",
+                            motion,
+                            pos
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_far_targets_on_large_files() {
+        let lines = (0..3000).map(|i| format!("let value_{} = compute(data, {});", i % 7, i));
+        let buffer = Buffer::from(lines.collect::<Vec<_>>());
+        let mut solver = MotionSolver::default();
+
+        let near = solver
+            .solve(
+                &buffer,
+                Position { row: 10, col: 0 },
+                Position { row: 40, col: 4 },
+            )
+            .unwrap();
+        assert_eq!(near.cost, 4);
+
+        // Counts stop at 999, so `880f8` jumps most of the way
+        let far = Position { row: 2900, col: 20 };
+        let route = solver
+            .solve(&buffer, Position { row: 0, col: 0 }, far)
+            .unwrap();
+        assert_eq!(route.target, far);
+        assert_eq!(route.cost, 7);
+    }
+}
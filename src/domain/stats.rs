@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, time::Instant};
+
+use crate::domain::{EventSubscriber, GameEvent, ScoreReason};
+
+/// Statistics collected over a single round.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundStats {
+    /// Number of enemies destroyed
+    pub enemies_destroyed: usize,
+    /// Number of hints requested
+    pub hints_used: usize,
+    /// Points deducted from the score for hints
+    pub hint_points_spent: usize,
+    /// Number of motions rejected by the drill
+    pub drill_errors: usize,
+    /// Number of times the player stepped onto a hazard or got shot
+    pub hazard_hits: usize,
+    /// Points earned, broken down by reason
+    pub points: BTreeMap<ScoreReason, usize>,
+    /// Number of motions applied to the cursor
+    pub motions: usize,
+    /// Number of motions that hit an enemy
+    pub motions_on_target: usize,
+    /// Whether the last motion already counted as on target
+    on_target: bool,
+}
+
+impl RoundStats {
+    /// Returns the percentage of motions that hit an enemy.
+    pub fn accuracy(&self) -> usize {
+        (self.motions_on_target * 100)
+            .checked_div(self.motions)
+            .unwrap_or(0)
+    }
+
+    /// Counts the last motion as on target, once however many enemies it hit.
+    fn hit_by_motion(&mut self) {
+        if self.motions > 0 && !self.on_target {
+            self.motions_on_target += 1;
+            self.on_target = true;
+        }
+    }
+}
+
+impl EventSubscriber for RoundStats {
+    fn on_event(&mut self, event: &GameEvent, _now: Instant) {
+        match event {
+            GameEvent::RoundStarted { .. } => *self = Self::default(),
+            GameEvent::MotionApplied { .. } => {
+                self.motions += 1;
+                self.on_target = false;
+            }
+            GameEvent::EnemyDestroyed { .. } => {
+                self.enemies_destroyed += 1;
+                self.hit_by_motion();
+            }
+            GameEvent::EnemyHit { .. } => self.hit_by_motion(),
+            GameEvent::ScoreAwarded {
+                base,
+                multiplier,
+                reason,
+            } => *self.points.entry(*reason).or_default() += base * multiplier,
+            GameEvent::HazardHit { .. } => self.hazard_hits += 1,
+            GameEvent::HintShown { cost, .. } => {
+                self.hints_used += 1;
+                self.hint_points_spent += cost;
+            }
+            GameEvent::MotionRejected { .. } => self.drill_errors += 1,
+            _ => {}
+        }
+    }
+}
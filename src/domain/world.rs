@@ -1,10 +1,18 @@
-use crate::domain::config::GameConfig;
-use crate::domain::enemies::Enemies;
-use crate::domain::events::GameEvent;
+use crate::domain::config::{GameConfig, GameRng};
+use crate::domain::enemies::{Enemies, EnemyId, MoveContext};
+use crate::domain::events::{GameEvent, HintRefusal, InputMode};
 use crate::domain::mechanics::{CollisionEvent, check_collisions};
-use crate::domain::motions::Motion;
-use crate::domain::{Buffer, Cursor, generate_random_rust_code_buffer, load_buffer_from_file};
-use std::time::Instant;
+use crate::domain::motions::{Motion, MotionSet, landing_points};
+use crate::domain::{
+    Buffer, Cursor, DEFAULT_MAX_COUNT, Drill, Hazards, MotionSolver, Position, PowerUpKind,
+    PowerUps, Route, Scenario, SharedClock, SystemClock, Violation, buffer_from_text,
+    generate_random_rust_code_buffer, is_scenario_path,
+    scoring::{Kill, Scoring},
+};
+use rand::SeedableRng;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::rc::Rc;
 
 /// The game world, containing the buffer, cursor, enemies, and game state.
 pub struct World {
@@ -14,10 +22,30 @@ pub struct World {
     cursor: Cursor,
     /// Currently active enemies
     enemies: Enemies,
+    /// Projectiles and lines on fire
+    hazards: Hazards,
+    /// Power-ups on the buffer and active effects
+    powerups: PowerUps,
+    /// Cells of the buffer that common motions land on directly
+    landing_points: HashSet<Position>,
     /// Events generated on this tick
     events: Vec<GameEvent>,
     /// Current score
     score: usize,
+    /// Combo chain and keystrokes behind the score
+    scoring: Scoring,
+    /// Last input mode reported by the player
+    input_mode: InputMode,
+    /// Solver used to compute hints
+    solver: MotionSolver,
+    /// Source of time for every timing decision
+    clock: SharedClock,
+    /// Seed the random number generator started from
+    seed: u64,
+    /// Text of the file the buffer was loaded from, if any
+    file_contents: Option<String>,
+    /// Source of every random decision
+    rng: GameRng,
     /// Config
     config: GameConfig,
 }
@@ -33,32 +61,133 @@ impl World {
     ///
     /// Buffer is loaded from file if path provided; otherwise, a random Rust code buffer is generated.
     pub fn new(config: GameConfig) -> Self {
-        let buffer = if let Some(ref path) = config.file_path {
-            load_buffer_from_file(path).unwrap_or_else(|_| generate_random_rust_code_buffer())
-        } else {
-            generate_random_rust_code_buffer()
-        };
+        Self::with_clock(config, Rc::new(SystemClock))
+    }
 
-        Self {
+    /// Creates a new World whose timing is driven by `clock`.
+    pub fn with_clock(config: GameConfig, clock: SharedClock) -> Self {
+        let seed = config.seed.unwrap_or_else(rand::random);
+        let mut rng = GameRng::seed_from_u64(seed);
+        let file_contents = Self::read_file(&config);
+        let (buffer, scenario) = Self::load_buffer(&config, file_contents.as_deref(), &mut rng);
+
+        let solver = Self::hint_solver(&config);
+        let now = clock.now();
+        let mut world = Self {
+            landing_points: landing_points(&buffer),
             buffer,
             cursor: Cursor::default(),
-            enemies: Enemies::new(&config.enemy),
+            enemies: Enemies::new(&config.enemy, now),
+            hazards: Hazards::new(&config.hazard, now),
+            powerups: PowerUps::new(&config.powerup, now),
             events: Vec::new(),
             config,
             score: 0,
+            scoring: Scoring::default(),
+            input_mode: InputMode::default(),
+            solver,
+            clock,
+            seed,
+            file_contents,
+            rng,
+        };
+        world.emit(GameEvent::RoundStarted { seed });
+        if let Some(scenario) = scenario {
+            world.place_scenario(&scenario);
+        }
+        world
+    }
+
+    /// Creates a World set up as `scenario`, standing still but for the player.
+    pub fn from_scenario(scenario: &Scenario, clock: SharedClock) -> Self {
+        let config = GameConfig {
+            seed: Some(0),
+            ..GameConfig::default().static_world()
+        };
+        let mut world = Self::with_clock(config, clock);
+        world.buffer = scenario.buffer();
+        world.file_contents = None;
+        world.landing_points = landing_points(&world.buffer);
+        world.place_scenario(scenario);
+        world
+    }
+
+    /// Returns the text of the configured file, from the config if it carries a copy.
+    fn read_file(config: &GameConfig) -> Option<String> {
+        let path = config.file_path.as_ref()?;
+        config
+            .file_contents
+            .clone()
+            .or_else(|| fs::read_to_string(path).ok())
+    }
+
+    /// Loads the configured file from its `contents`, falling back to random code if
+    /// there is none or it fails to load.
+    ///
+    /// Scenario files are returned too, to place their cursor and enemies.
+    fn load_buffer(
+        config: &GameConfig,
+        contents: Option<&str>,
+        rng: &mut GameRng,
+    ) -> (Buffer, Option<Scenario>) {
+        match (&config.file_path, contents) {
+            (Some(path), Some(contents)) if is_scenario_path(path) => {
+                match Scenario::parse(contents) {
+                    Ok(scenario) => (scenario.buffer(), Some(scenario)),
+                    Err(_) => (generate_random_rust_code_buffer(rng), None),
+                }
+            }
+            (Some(_), Some(contents)) => (buffer_from_text(contents), None),
+            _ => (generate_random_rust_code_buffer(rng), None),
+        }
+    }
+
+    /// Moves the cursor to the start of `scenario` and places its enemies.
+    fn place_scenario(&mut self, scenario: &Scenario) {
+        self.cursor.move_to(scenario.cursor);
+        let now = self.clock.now();
+        for &(position, kind) in &scenario.enemies {
+            if self.enemies.spawn(kind, vec![position], now) {
+                self.emit(GameEvent::EnemySpawned { position, kind });
+            }
         }
     }
 
     pub fn reset(&mut self) {
         self.cursor.reset();
-        self.buffer = if let Some(ref path) = self.config.file_path {
-            load_buffer_from_file(path).unwrap_or_else(|_| generate_random_rust_code_buffer())
-        } else {
-            generate_random_rust_code_buffer()
-        };
-        self.enemies = Enemies::new(&self.config.enemy);
+        self.seed = self.config.seed.unwrap_or_else(rand::random);
+        self.rng = GameRng::seed_from_u64(self.seed);
+        self.file_contents = Self::read_file(&self.config);
+        let (buffer, scenario) =
+            Self::load_buffer(&self.config, self.file_contents.as_deref(), &mut self.rng);
+        self.buffer = buffer;
+        self.landing_points = landing_points(&self.buffer);
+        let now = self.clock.now();
+        self.enemies = Enemies::new(&self.config.enemy, now);
+        self.hazards = Hazards::new(&self.config.hazard, now);
+        self.powerups = PowerUps::new(&self.config.powerup, now);
         self.events.clear();
         self.score = 0;
+        self.scoring = Scoring::default();
+        self.input_mode = InputMode::default();
+        self.solver.clear();
+        self.emit(GameEvent::RoundStarted { seed: self.seed });
+        if let Some(scenario) = scenario {
+            self.place_scenario(&scenario);
+        }
+    }
+
+    /// Builds the hint solver, limited to the drill's motions if there is one.
+    fn hint_solver(config: &GameConfig) -> MotionSolver {
+        let motions = config
+            .drill
+            .map_or_else(MotionSet::all, |drill| drill.motion_set());
+        MotionSolver::new(motions, DEFAULT_MAX_COUNT)
+    }
+
+    /// Returns the drill of the round, if any.
+    pub fn drill(&self) -> Option<&Drill> {
+        self.config.drill.as_ref()
     }
 
     /// Returns a reference to the current text buffer.
@@ -81,47 +210,399 @@ impl World {
         &self.enemies
     }
 
+    /// Returns the projectiles and lines on fire.
+    pub fn hazards(&self) -> &Hazards {
+        &self.hazards
+    }
+
+    /// Returns the seed of the round, replaying it when set as `GameConfig::seed`.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the text of the file the buffer was loaded from, None for generated code.
+    pub fn file_contents(&self) -> Option<&str> {
+        self.file_contents.as_deref()
+    }
+
+    /// Returns the clock driving the world.
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Returns the power-ups on the buffer and the active effects.
+    pub fn powerups(&self) -> &PowerUps {
+        &self.powerups
+    }
+
+    /// Returns the cells of the buffer that common motions land on directly.
+    pub fn landing_points(&self) -> &HashSet<Position> {
+        &self.landing_points
+    }
+
     /// Returns the current score.
     pub fn score(&self) -> usize {
         self.score
     }
 
+    /// Records an event for the next pull.
+    fn emit(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+
+    /// Reports a change of the player's input mode.
+    pub fn set_input_mode(&mut self, mode: InputMode) {
+        if mode != self.input_mode {
+            self.input_mode = mode;
+            self.emit(GameEvent::ModeChanged { mode });
+        }
+    }
+
+    /// Ends the round, reporting the final score.
+    pub fn end_round(&mut self) {
+        self.emit(GameEvent::RoundEnded { score: self.score });
+    }
+
     /// Pull (consume) all events generated since last pull
     pub fn pull_events(&mut self) -> Vec<GameEvent> {
         std::mem::take(&mut self.events)
     }
 
     /// Apply motion to cursor and handle resulting events
+    ///
+    /// Landing on an enemy armored against the motion bounces the cursor back.
     pub fn apply_motion(&mut self, motion: Motion, count: Option<usize>) {
+        let now = self.clock.now();
         let old_pos = self.cursor.pos();
-        self.cursor.apply_motion(&self.buffer, motion, count);
-        let new_pos = self.cursor.pos();
+        self.cursor.apply_motion(&self.buffer, motion, count, now);
+        self.emit(GameEvent::MotionApplied { motion, count });
+
+        // Check for collisions
+        let collision_events = check_collisions(self.cursor.pos(), &motion, &self.enemies);
+        for event in collision_events {
+            match event {
+                CollisionEvent::PlayerHitEnemy {
+                    position,
+                    enemy_id,
+                    kind,
+                } => match self.enemies.hit(&enemy_id.into(), position, &motion) {
+                    Some(0) => {
+                        let kill = Kill {
+                            points: kind.score(),
+                            motion: Some((motion, count)),
+                            distance: old_pos.row.abs_diff(position.row)
+                                + old_pos.col.abs_diff(position.col),
+                        };
+                        self.enemy_destroyed(position, &kill);
+                    }
+                    Some(remaining) => {
+                        self.emit(GameEvent::EnemyHit {
+                            position,
+                            remaining,
+                        });
+                    }
+                    None => {}
+                },
+                CollisionEvent::PlayerBounced { position, kind, .. } => {
+                    self.cursor.move_to(old_pos);
+                    self.emit(GameEvent::EnemyDeflected { position, kind });
+                }
+            }
+        }
 
         // Generate cursor moved event if position changed
+        let new_pos = self.cursor.pos();
         if old_pos != new_pos {
-            self.events.push(GameEvent::CursorMoved {
+            self.emit(GameEvent::CursorMoved {
                 position: new_pos,
-                timestamp: Instant::now(),
+                timestamp: now,
             });
+
+            if self.hazards.step_onto(new_pos) {
+                self.hurt(new_pos);
+            }
+            if let Some(kind) = self.powerups.collect_at(new_pos, now) {
+                self.activate_powerup(new_pos, kind);
+            }
         }
+    }
 
-        // Check for collisions
-        let collision_events = check_collisions(new_pos, &self.enemies);
-        for event in collision_events {
-            match event {
-                CollisionEvent::PlayerHitEnemy { position, enemy_id } => {
-                    self.enemies.destroy(&enemy_id.into());
-                    self.score += 1;
-                    self.events.push(GameEvent::EnemyDestroyed { position });
-                }
+    /// Scores an enemy destroyed at `position`, awarding combo and bonus points.
+    fn enemy_destroyed(&mut self, position: Position, kill: &Kill) {
+        self.emit(GameEvent::EnemyDestroyed { position });
+
+        let awards =
+            self.scoring
+                .score_kill(kill, self.powerups.score_multiplier(), self.clock.now());
+        for award in awards {
+            self.score += award.points();
+            self.emit(GameEvent::ScoreAwarded {
+                base: award.base,
+                multiplier: award.multiplier,
+                reason: award.reason,
+            });
+        }
+    }
+
+    /// Counts a key typed by the player, for the keystroke efficiency bonus.
+    pub fn record_keystroke(&mut self) {
+        self.scoring.record_keystroke();
+    }
+
+    /// Starts the effect of a power-up collected at `position`.
+    ///
+    /// Timed effects are handled by `PowerUps`; a bomb goes off at once.
+    fn activate_powerup(&mut self, position: Position, kind: PowerUpKind) {
+        self.emit(GameEvent::PowerUpCollected { position, kind });
+
+        if kind == PowerUpKind::Bomb {
+            let rows = self.buffer.paragraph_rows(position.row);
+            for (position, kind) in self.enemies.destroy_in_rows(rows) {
+                let kill = Kill {
+                    points: kind.score(),
+                    motion: None,
+                    distance: 0,
+                };
+                self.enemy_destroyed(position, &kill);
             }
         }
     }
 
+    /// Takes the hazard penalty off the score after the player got hit at `position`.
+    fn hurt(&mut self, position: Position) {
+        let penalty = self.hazards.penalty().min(self.score);
+        self.score -= penalty;
+        self.emit(GameEvent::HazardHit { position, penalty });
+    }
+
+    /// Finds the cheapest route to the nearest enemy by keystrokes, deducting the hint cost
+    ///
+    /// Enemies the route's last motion would bounce off, through armor or a repeated
+    /// boss hit, are skipped. The cost is capped at the current score, so hints are
+    /// always available. Returns None without charging anything, and reports why, if
+    /// no enemy can be reached.
+    pub fn request_hint(&mut self) -> Option<Route> {
+        let targets = self.enemies.position_set();
+        if targets.is_empty() {
+            self.emit(GameEvent::HintRefused {
+                reason: HintRefusal::NoEnemies,
+            });
+            return None;
+        }
+
+        let enemies = &self.enemies;
+        let accept = |target: Position, motion: Option<Motion>| {
+            motion.is_none_or(|motion| {
+                enemies
+                    .iter()
+                    .filter(|enemy| enemy.occupies(target))
+                    .all(|enemy| enemy.accepts_hit(target, &motion))
+            })
+        };
+        let Some(route) =
+            self.solver
+                .solve_nearest_where(&self.buffer, self.cursor.pos(), &targets, &accept)
+        else {
+            self.emit(GameEvent::HintRefused {
+                reason: HintRefusal::Unreachable,
+            });
+            return None;
+        };
+
+        let cost = self.config.hint_cost.min(self.score);
+        self.score -= cost;
+        self.emit(GameEvent::HintShown {
+            route: route.clone(),
+            cost,
+        });
+        Some(route)
+    }
+
+    /// Records a motion rejected by the drill as an error
+    pub fn reject_motion(&mut self, violation: Violation) {
+        self.emit(GameEvent::MotionRejected { violation });
+    }
+
     /// Advance the game state by one tick
     ///
-    /// Currently only enemies move each tick.
+    /// Enemies move and shoot unless frozen, then projectiles fly and may hit the cursor.
     pub fn tick(&mut self) {
-        self.enemies.tick(&self.buffer);
+        let now = self.clock.now();
+        let cursor = self.cursor.pos();
+        let ctx = MoveContext {
+            buffer: &self.buffer,
+            cursor,
+            landing_points: &self.landing_points,
+            now,
+        };
+        let before = self.enemy_positions();
+        if !self.powerups.is_active(PowerUpKind::Freeze) {
+            let shots = self.enemies.tick(&ctx, &mut self.rng);
+            for shot in shots {
+                self.hazards.fire(&self.buffer, shot);
+            }
+        }
+        if self.powerups.should_pull(now) {
+            self.enemies.pull_toward_cursor(&ctx);
+        }
+        self.report_enemy_moves(&before);
+
+        for kind in self.powerups.tick(&self.buffer, cursor, now, &mut self.rng) {
+            self.emit(GameEvent::PowerUpExpired { kind });
+        }
+
+        self.hazards.tick(&self.buffer, cursor, now, &mut self.rng);
+        if self.hazards.take_projectile_at(cursor) {
+            self.hurt(cursor);
+        }
+    }
+
+    /// Returns where each enemy stands, by ID.
+    fn enemy_positions(&self) -> BTreeMap<EnemyId, Position> {
+        self.enemies.iter().map(|e| (e.id(), e.pos())).collect()
+    }
+
+    /// Reports the enemies spawned or moved since their positions were `before`.
+    fn report_enemy_moves(&mut self, before: &BTreeMap<EnemyId, Position>) {
+        let changes: Vec<GameEvent> = self
+            .enemies
+            .iter()
+            .filter_map(|e| match before.get(&e.id()) {
+                None => Some(GameEvent::EnemySpawned {
+                    position: e.pos(),
+                    kind: e.kind(),
+                }),
+                Some(&from) if from != e.pos() => Some(GameEvent::EnemyMoved { from, to: e.pos() }),
+                Some(_) => None,
+            })
+            .collect();
+        for event in changes {
+            self.emit(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod world_tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::domain::{ManualClock, enemies::EnemyKind, motions::MotionKind};
+
+    #[test]
+    fn test_enemies_spawn_on_clock_time() {
+        let clock = Rc::new(ManualClock::new());
+        let config = GameConfig::default();
+        let spawn_interval = config.enemy.spawn_interval;
+        let mut world = World::with_clock(config, clock.clone());
+
+        world.tick();
+        assert_eq!(world.enemies().iter().count(), 0);
+
+        clock.advance(spawn_interval);
+        world.tick();
+        assert_eq!(world.enemies().iter().count(), 1);
+    }
+
+    #[test]
+    fn test_scenario_world_stands_still() {
+        let scenario =
+            Scenario::parse("enemy: 0:4\nenemy: 1:0 chaser\n=== buffer\nlet $0x = 1;\nx\n")
+                .unwrap();
+        let clock = Rc::new(ManualClock::new());
+        let mut world = World::from_scenario(&scenario, clock.clone());
+        assert_eq!(world.buffer().lines(), ["let x = 1;", "x"]);
+        assert_eq!(world.cursor().pos(), Position { row: 0, col: 4 });
+
+        for _ in 0..100 {
+            clock.advance(Duration::from_secs(1));
+            world.tick();
+        }
+        let mut enemies = world.enemies().positions();
+        enemies.sort_by_key(|pos| (pos.row, pos.col));
+        assert_eq!(
+            enemies,
+            [Position { row: 0, col: 4 }, Position { row: 1, col: 0 }]
+        );
+    }
+
+    #[test]
+    fn test_same_seed_plays_out_alike() {
+        let run = || {
+            let clock = Rc::new(ManualClock::new());
+            let config = GameConfig {
+                seed: Some(42),
+                ..GameConfig::default()
+            };
+            let mut world = World::with_clock(config, clock.clone());
+            for _ in 0..200 {
+                clock.advance(Duration::from_millis(100));
+                world.tick();
+            }
+            let mut enemies: Vec<(Position, EnemyKind)> = world
+                .enemies()
+                .iter()
+                .map(|e| (e.pos(), e.kind()))
+                .collect();
+            enemies.sort_by_key(|(pos, _)| (pos.row, pos.col));
+            (world.buffer().lines().to_vec(), enemies)
+        };
+
+        let (buffer, enemies) = run();
+        assert!(!enemies.is_empty());
+        assert_eq!(run(), (buffer, enemies));
+    }
+
+    #[test]
+    fn test_hint_lands_with_a_motion_that_hits() {
+        let scenario =
+            Scenario::parse("enemy: 0:4 armored-basic\n=== buffer\nlet value = compute(data);\n")
+                .unwrap();
+        let clock = Rc::new(ManualClock::new());
+        let mut world = World::from_scenario(&scenario, clock);
+        let hint_cost = world.config.hint_cost;
+
+        // `w` is cheaper but bounces off the armor
+        world.score = hint_cost - 3;
+        let route = world.request_hint().unwrap();
+        assert_eq!(route.target, Position { row: 0, col: 4 });
+        assert_eq!(route.keys(), "4l");
+        // A score too low to pay for the hint is used up instead of refusing it
+        assert_eq!(world.score(), 0);
+
+        world.score = hint_cost + 5;
+        assert!(world.request_hint().is_some());
+        assert_eq!(world.score(), 5);
+    }
+
+    #[test]
+    fn test_hint_refused_without_reachable_enemies() {
+        let refusal = |contents: &str, motions: MotionSet| {
+            let scenario = Scenario::parse(contents).unwrap();
+            let mut world = World::from_scenario(&scenario, Rc::new(ManualClock::new()));
+            world.score = 50;
+            world.solver = MotionSolver::new(motions, DEFAULT_MAX_COUNT);
+
+            assert_eq!(world.request_hint(), None);
+            assert_eq!(world.score(), 50);
+            world.events.iter().find_map(|event| match event {
+                GameEvent::HintRefused { reason } => Some(*reason),
+                _ => None,
+            })
+        };
+
+        let buffer = "=== buffer\nlet value = compute(data);\n";
+        assert_eq!(
+            refusal(buffer, MotionSet::all()),
+            Some(HintRefusal::NoEnemies)
+        );
+        // `h` never gets the cursor right of the start
+        assert_eq!(
+            refusal(
+                &format!("enemy: 0:4\n{}", buffer),
+                MotionSet::only(&[MotionKind::Left])
+            ),
+            Some(HintRefusal::Unreachable)
+        );
     }
 }
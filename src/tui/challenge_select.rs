@@ -0,0 +1,76 @@
+use std::{fs, path::PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Directory challenge files are listed from.
+pub const CHALLENGE_DIR: &str = "challenges";
+/// Extension of challenge files.
+pub const CHALLENGE_EXTENSION: &str = "challenge";
+
+/// Actions as a result of challenge selection input.
+pub enum ChallengeSelectAction {
+    Start(PathBuf),
+    Cancel,
+    Noop,
+}
+
+/// Manages the list of challenge files.
+#[derive(Default)]
+pub struct ChallengeSelector {
+    files: Vec<PathBuf>,
+    selected: usize,
+    error: Option<String>,
+}
+
+impl ChallengeSelector {
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    pub fn error(&self) -> &Option<String> {
+        &self.error
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Lists the challenge files in the challenge directory again.
+    pub fn refresh(&mut self) {
+        self.files = fs::read_dir(CHALLENGE_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|e| e == CHALLENGE_EXTENSION))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.files.sort();
+        self.selected = self.selected.min(self.files.len().saturating_sub(1));
+        self.error = None;
+    }
+
+    /// Handles a key event in the challenge list.
+    pub fn handle_key(&mut self, key: KeyEvent) -> ChallengeSelectAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.files.len().saturating_sub(1));
+                ChallengeSelectAction::Noop
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                ChallengeSelectAction::Noop
+            }
+            KeyCode::Enter => match self.files.get(self.selected) {
+                Some(path) => ChallengeSelectAction::Start(path.clone()),
+                None => ChallengeSelectAction::Noop,
+            },
+            KeyCode::Esc => ChallengeSelectAction::Cancel,
+            _ => ChallengeSelectAction::Noop,
+        }
+    }
+}
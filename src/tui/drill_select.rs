@@ -0,0 +1,39 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::domain::DRILLS;
+
+/// Actions as a result of drill selection input.
+pub enum DrillSelectAction {
+    Start(usize),
+    Cancel,
+    Noop,
+}
+
+/// Manages the drill selection list.
+#[derive(Default)]
+pub struct DrillSelector {
+    selected: usize,
+}
+
+impl DrillSelector {
+    pub fn selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    /// Handles a key event in the drill list.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DrillSelectAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(DRILLS.len() - 1);
+                DrillSelectAction::Noop
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                DrillSelectAction::Noop
+            }
+            KeyCode::Enter => DrillSelectAction::Start(self.selected),
+            KeyCode::Esc => DrillSelectAction::Cancel,
+            _ => DrillSelectAction::Noop,
+        }
+    }
+}
@@ -1,9 +1,14 @@
 use std::{
+    cell::RefCell,
     collections::HashMap,
+    rc::Rc,
     time::{Duration, Instant},
 };
 
-use crate::{domain::Position, tui::theme::*};
+use crate::{
+    domain::{EventSubscriber, GameEvent, Position, SharedClock},
+    tui::theme::*,
+};
 
 /// A visual effect in the TUI.
 pub struct Effect {
@@ -16,14 +21,43 @@ pub struct Effect {
 pub enum EffectType {
     Collision,
     Trailing,
+    GhostTrailing,
+    GhostKill,
+    Deflect,
+    Burn,
+    Sparkle,
 }
 
 impl Effect {
-    pub fn collision(position: Position) -> Self {
+    pub fn collision(position: Position, timestamp: Instant) -> Self {
         Self {
             ty: EffectType::Collision,
             position,
-            timestamp: Instant::now(),
+            timestamp,
+        }
+    }
+
+    pub fn deflect(position: Position, timestamp: Instant) -> Self {
+        Self {
+            ty: EffectType::Deflect,
+            position,
+            timestamp,
+        }
+    }
+
+    pub fn burn(position: Position, timestamp: Instant) -> Self {
+        Self {
+            ty: EffectType::Burn,
+            position,
+            timestamp,
+        }
+    }
+
+    pub fn sparkle(position: Position, timestamp: Instant) -> Self {
+        Self {
+            ty: EffectType::Sparkle,
+            position,
+            timestamp,
         }
     }
 
@@ -35,16 +69,37 @@ impl Effect {
         }
     }
 
-    pub fn percentage_elapsed(&self) -> f32 {
+    pub fn ghost_trailing(position: Position, timestamp: Instant) -> Self {
+        Self {
+            ty: EffectType::GhostTrailing,
+            position,
+            timestamp,
+        }
+    }
+
+    pub fn ghost_kill(position: Position, timestamp: Instant) -> Self {
+        Self {
+            ty: EffectType::GhostKill,
+            position,
+            timestamp,
+        }
+    }
+
+    /// Returns the fraction of the effect's duration elapsed at `now`.
+    pub fn percentage_elapsed(&self, now: Instant) -> f32 {
         let total_duration = self.duration().as_secs_f32();
-        let elapsed = Instant::now().duration_since(self.timestamp).as_secs_f32();
+        let elapsed = now.duration_since(self.timestamp).as_secs_f32();
         (elapsed / total_duration).clamp(0.0, 1.0)
     }
 
     pub fn duration(&self) -> Duration {
         match self.ty {
             EffectType::Collision => COLLISION_EFFECT_DURATION,
-            EffectType::Trailing => TRAILING_EFFECT_DURATION,
+            EffectType::Trailing | EffectType::GhostTrailing => TRAILING_EFFECT_DURATION,
+            EffectType::GhostKill => GHOST_KILL_EFFECT_DURATION,
+            EffectType::Deflect => DEFLECT_EFFECT_DURATION,
+            EffectType::Burn => BURN_EFFECT_DURATION,
+            EffectType::Sparkle => SPARKLE_EFFECT_DURATION,
         }
     }
 }
@@ -64,9 +119,58 @@ impl Effects {
         self.map.insert(effect.position, effect);
     }
 
-    pub fn cleanup(&mut self) {
-        let now = Instant::now();
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes the effects expired at `now`.
+    pub fn cleanup(&mut self, now: Instant) {
         self.map
             .retain(|_, effect| now.duration_since(effect.timestamp) < effect.duration());
     }
 }
+
+impl EventSubscriber for Effects {
+    fn on_event(&mut self, event: &GameEvent, now: Instant) {
+        let effect = match event {
+            GameEvent::EnemyDestroyed { position } | GameEvent::EnemyHit { position, .. } => {
+                Effect::collision(*position, now)
+            }
+            GameEvent::EnemyDeflected { position, .. } => Effect::deflect(*position, now),
+            GameEvent::HazardHit { position, .. } => Effect::burn(*position, now),
+            GameEvent::PowerUpCollected { position, .. } => Effect::sparkle(*position, now),
+            GameEvent::CursorMoved {
+                position,
+                timestamp,
+            } => Effect::trailing(*position, *timestamp),
+            _ => return,
+        };
+        self.spawn_effect(effect);
+    }
+}
+
+/// Leaves a dim trail behind a raced ghost and marks the enemies it killed.
+pub struct GhostTrail {
+    effects: Rc<RefCell<Effects>>,
+    /// Clock of the live round
+    clock: SharedClock,
+}
+
+impl GhostTrail {
+    pub fn new(effects: Rc<RefCell<Effects>>, clock: SharedClock) -> Self {
+        Self { effects, clock }
+    }
+}
+
+impl EventSubscriber for GhostTrail {
+    fn on_event(&mut self, event: &GameEvent, _now: Instant) {
+        // The ghost's round runs on its own clock, so time effects with ours
+        let now = self.clock.now();
+        let effect = match event {
+            GameEvent::CursorMoved { position, .. } => Effect::ghost_trailing(*position, now),
+            GameEvent::EnemyDestroyed { position } => Effect::ghost_kill(*position, now),
+            _ => return,
+        };
+        self.effects.borrow_mut().spawn_effect(effect);
+    }
+}
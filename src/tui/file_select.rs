@@ -1,5 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::domain::{Difficulty, enemies::SpawnRule, is_scenario_path};
+
 /// Actions as a result of file selection input.
 pub enum FileSelectAction {
     Confirm(String),
@@ -12,6 +14,10 @@ pub enum FileSelectAction {
 pub struct FileSelector {
     input: String,
     error: Option<String>,
+    /// Difficulty of the next round
+    difficulty: Difficulty,
+    /// Where enemies of the next round spawn, None for the drill's rule or anywhere
+    spawn_rule: Option<SpawnRule>,
 }
 
 impl FileSelector {
@@ -19,6 +25,8 @@ impl FileSelector {
         Self {
             input: default_path.to_string(),
             error: None,
+            difficulty: Difficulty::default(),
+            spawn_rule: None,
         }
     }
 
@@ -30,6 +38,14 @@ impl FileSelector {
         &self.error
     }
 
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn spawn_rule(&self) -> Option<SpawnRule> {
+        self.spawn_rule
+    }
+
     /// Handles a key event in the file selector.
     pub fn handle_key(&mut self, key: KeyEvent) -> FileSelectAction {
         match (key.code, key.modifiers) {
@@ -40,13 +56,26 @@ impl FileSelector {
                 if self.input.is_empty() {
                     self.error = Some("Please enter a file path".to_string());
                     FileSelectAction::Noop
-                } else if !self.input.ends_with(".rs") {
-                    self.error = Some("File must be a .rs file".to_string());
+                } else if !self.input.ends_with(".rs") && !is_scenario_path(&self.input) {
+                    self.error = Some("File must be a .rs or .scenario file".to_string());
                     FileSelectAction::Noop
                 } else {
                     FileSelectAction::Confirm(self.input.clone())
                 }
             }
+            // Pick the next difficulty
+            (KeyCode::Tab, _) => {
+                self.difficulty = self.difficulty.next();
+                FileSelectAction::Noop
+            }
+            // Pick the next spawn rule, back to the default after the last one
+            (KeyCode::BackTab, _) => {
+                self.spawn_rule = match self.spawn_rule {
+                    None => Some(SpawnRule::ALL[0]),
+                    Some(rule) => rule.next(),
+                };
+                FileSelectAction::Noop
+            }
             // Cancel file selection
             (KeyCode::Esc, _) => FileSelectAction::Cancel,
             // Handle backspace
@@ -65,6 +94,10 @@ impl FileSelector {
         }
     }
 
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
     pub fn reset(&mut self, default_path: &str) {
         self.input = default_path.to_string();
         self.error = None;
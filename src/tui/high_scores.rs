@@ -0,0 +1,67 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::domain::{HighScores, ScoreKey};
+
+/// Actions as a result of high score browsing input.
+pub enum HighScoresAction {
+    Cancel,
+    Noop,
+}
+
+/// Actions as a result of input on the results of a round.
+pub enum ResultsAction {
+    Continue,
+    Noop,
+}
+
+/// Manages the list of high score tables, one per file, mode and difficulty.
+#[derive(Default)]
+pub struct HighScoreBrowser {
+    keys: Vec<ScoreKey>,
+    selected: usize,
+}
+
+impl HighScoreBrowser {
+    pub fn keys(&self) -> &[ScoreKey] {
+        &self.keys
+    }
+
+    pub fn selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns the table being shown, if any.
+    pub fn selected_key(&self) -> Option<&ScoreKey> {
+        self.keys.get(self.selected)
+    }
+
+    /// Lists the tables of `scores` again.
+    pub fn refresh(&mut self, scores: &HighScores) {
+        self.keys = scores.keys();
+        self.selected = self.selected.min(self.keys.len().saturating_sub(1));
+    }
+
+    /// Handles a key event in the high score tables.
+    pub fn handle_key(&mut self, key: KeyEvent) -> HighScoresAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.keys.len().saturating_sub(1));
+                HighScoresAction::Noop
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                HighScoresAction::Noop
+            }
+            KeyCode::Esc | KeyCode::Char('q') => HighScoresAction::Cancel,
+            _ => HighScoresAction::Noop,
+        }
+    }
+}
+
+/// Handles a key event on the results of a round.
+pub fn handle_results_key(key: KeyEvent) -> ResultsAction {
+    match key.code {
+        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => ResultsAction::Continue,
+        _ => ResultsAction::Noop,
+    }
+}
@@ -0,0 +1,46 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::domain::{LESSONS, LessonProgress};
+
+/// Actions as a result of lesson selection input.
+pub enum LessonSelectAction {
+    Start(usize),
+    Cancel,
+    Noop,
+}
+
+/// Manages the lesson selection list.
+#[derive(Default)]
+pub struct LessonSelector {
+    selected: usize,
+}
+
+impl LessonSelector {
+    pub fn selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects the lesson at `index`, e.g. to move on to the next lesson.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(LESSONS.len() - 1);
+    }
+
+    /// Handles a key event in the lesson list, only unlocked lessons can be started.
+    pub fn handle_key(&mut self, key: KeyEvent, progress: &LessonProgress) -> LessonSelectAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.select(self.selected + 1);
+                LessonSelectAction::Noop
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.select(self.selected.saturating_sub(1));
+                LessonSelectAction::Noop
+            }
+            KeyCode::Enter if progress.is_unlocked(self.selected) => {
+                LessonSelectAction::Start(self.selected)
+            }
+            KeyCode::Esc => LessonSelectAction::Cancel,
+            _ => LessonSelectAction::Noop,
+        }
+    }
+}
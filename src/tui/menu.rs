@@ -6,6 +6,11 @@ use crossterm::event::{KeyCode, KeyEvent};
 #[derive(Clone, Copy)]
 pub enum MenuOption {
     Start,
+    Drill,
+    Challenges,
+    Lessons,
+    Replays,
+    HighScores,
     Quit,
 }
 
@@ -13,6 +18,11 @@ impl Display for MenuOption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let text = match self {
             MenuOption::Start => "Start",
+            MenuOption::Drill => "Drills",
+            MenuOption::Challenges => "Challenges",
+            MenuOption::Lessons => "Lessons",
+            MenuOption::Replays => "Replays",
+            MenuOption::HighScores => "High Scores",
             MenuOption::Quit => "Quit",
         };
         write!(f, "{}", text)
@@ -22,6 +32,11 @@ impl Display for MenuOption {
 /// Actions as a result of menu input.
 pub enum MenuAction {
     Start,
+    Drill,
+    Challenges,
+    Lessons,
+    Replays,
+    HighScores,
     Quit,
     Noop,
 }
@@ -36,7 +51,15 @@ impl Default for Menu {
     fn default() -> Self {
         Self {
             selected: 0,
-            options: vec![MenuOption::Start, MenuOption::Quit],
+            options: vec![
+                MenuOption::Start,
+                MenuOption::Drill,
+                MenuOption::Challenges,
+                MenuOption::Lessons,
+                MenuOption::Replays,
+                MenuOption::HighScores,
+                MenuOption::Quit,
+            ],
         }
     }
 }
@@ -79,6 +102,11 @@ impl Menu {
             }
             KeyCode::Enter => match self.selected_option() {
                 MenuOption::Start => MenuAction::Start,
+                MenuOption::Drill => MenuAction::Drill,
+                MenuOption::Challenges => MenuAction::Challenges,
+                MenuOption::Lessons => MenuAction::Lessons,
+                MenuOption::Replays => MenuAction::Replays,
+                MenuOption::HighScores => MenuAction::HighScores,
                 MenuOption::Quit => MenuAction::Quit,
             },
             _ => MenuAction::Noop,
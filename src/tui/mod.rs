@@ -1,16 +1,30 @@
+mod challenge_select;
+mod drill_select;
 mod effects;
 mod file_select;
+mod high_scores;
+mod lesson_select;
 mod menu;
 mod renderer;
+mod replay_select;
+mod screenshot;
 mod syntax;
 mod theme;
 mod ui;
 mod viewport;
 
-pub use effects::{Effect, EffectType, Effects};
+pub use challenge_select::{
+    CHALLENGE_DIR, CHALLENGE_EXTENSION, ChallengeSelectAction, ChallengeSelector,
+};
+pub use drill_select::{DrillSelectAction, DrillSelector};
+pub use effects::{Effect, EffectType, Effects, GhostTrail};
 pub use file_select::{FileSelectAction, FileSelector};
+pub use high_scores::{HighScoreBrowser, HighScoresAction, ResultsAction, handle_results_key};
+pub use lesson_select::{LessonSelectAction, LessonSelector};
 pub use menu::Menu;
 pub use renderer::*;
+pub use replay_select::{REPLAY_EXTENSION, ReplaySelectAction, ReplaySelector};
+pub use screenshot::{ScreenshotFormat, capture, export, screenshot};
 pub use theme::*;
 pub use ui::{UiAction, UiManager};
 pub use viewport::Viewport;
@@ -1,96 +1,157 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    time::{Duration, Instant},
+};
+
 use crate::{
-    domain::{Position, World},
-    tui::{Effect, EffectType, Effects, menu::Menu, syntax, theme::*, viewport::Viewport},
+    domain::{
+        Buffer, ChallengeRun, DRILLS, DiffLine, Difficulty, Exercise, HighScore, HighScores,
+        LESSONS, Lesson, LessonPhase, LessonProgress, Position, PowerUpKind, RoundStats, Route,
+        ScoreKey, ScoreReason, World,
+        enemies::{EnemyKind, SpawnRule},
+        format_date,
+        syntax::tokenize_line,
+    },
+    tui::{
+        CHALLENGE_DIR, CHALLENGE_EXTENSION, ChallengeSelector, DrillSelector, Effect, EffectType,
+        Effects, HighScoreBrowser, LessonSelector, REPLAY_EXTENSION, ReplaySelector, menu::Menu,
+        syntax, theme::*, viewport::Viewport,
+    },
 };
 use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table, Wrap},
 };
 
+/// What the lesson screen needs to know about a running lesson.
+pub struct LessonView<'a> {
+    pub index: usize,
+    pub lesson: &'a Lesson,
+    pub phase: LessonPhase,
+    pub exercise: &'a Exercise,
+    pub demo_finished: bool,
+}
+
+/// What the results screen needs to know about the round that just ended.
+pub struct ResultsView<'a> {
+    pub score: usize,
+    pub keystrokes: usize,
+    /// Percentage of motions that hit an enemy
+    pub accuracy: usize,
+    /// Points earned, broken down by reason
+    pub points: &'a BTreeMap<ScoreReason, usize>,
+    /// Table the round was played for
+    pub key: &'a ScoreKey,
+    /// Best scores of the table, best first
+    pub scores: Vec<&'a HighScore>,
+    /// Rank the round made in the table, if any
+    pub rank: Option<usize>,
+}
+
+/// What the replay bar needs to know about the playback.
+pub struct ReplayView {
+    pub position: Duration,
+    pub duration: Duration,
+    pub speed: f32,
+    pub paused: bool,
+    /// Final score of the recorded round
+    pub score: usize,
+}
+
+/// What the round needs to know about the ghost being raced.
+pub struct GhostView {
+    pub cursor: Position,
+    pub score: usize,
+}
+
 /// Renders the main game world (the editor) and visual effects.
 pub fn render_world(
     f: &mut Frame,
     world: &World,
     effects: &Effects,
+    hint: Option<&Route>,
+    ghost: Option<Position>,
     viewport: &Viewport,
     area: Rect,
 ) {
     let cursor = world.cursor();
-    let enemies = world.enemies().position_set();
+    let enemies = world.enemies().kind_map();
+    let projectiles = world.hazards().projectile_set();
+    let powerups: HashMap<Position, PowerUpKind> = world
+        .powerups()
+        .items()
+        .iter()
+        .map(|item| (item.position, item.kind))
+        .collect();
+    let enemy_style = if world.powerups().is_active(PowerUpKind::Freeze) {
+        Style::default()
+            .fg(FROZEN_ENEMY_COLOR)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    let hint_path: &[Position] = hint.map_or(&[], |h| &h.path);
     let buffer = world.buffer();
-    let mut lines = vec![];
+    let now = world.clock().now();
 
     // Calculate visible area
     let visible_height = area.height.saturating_sub(BORDER_LENGTH) as usize;
     let viewport_line_start = viewport.visible_line_start();
     let end_row = (viewport_line_start + visible_height).min(buffer.rows());
 
-    // Only render visible lines
-    for row in viewport_line_start..end_row {
-        if let Some(line_content) = buffer.get_line(row) {
-            let mut spans = vec![];
-
-            // Tokenize line for syntax highlighting
-            let tokens = syntax::tokenize_line(line_content);
-            let mut col = 0;
-
-            // Draw each token with appropriate style
-            for token in tokens {
-                for ch in token.text.chars() {
-                    let pos = Position { row, col };
-
-                    // Handle game elements rendering with a hierarchy
-                    let (display_ch, style) = if let Some(effect) = effects.get(&pos) {
-                        // Render effect
-                        draw_effect(effect)
-                    } else if pos == cursor.pos() {
-                        // Render cursor
-                        (
-                            PLAYER_CHAR.to_string(),
-                            Style::default().add_modifier(Modifier::BOLD),
-                        )
-                    } else if enemies.contains(&pos) {
-                        // Render enemies
-                        (
-                            ENEMY_CHAR.to_string(),
-                            Style::default().add_modifier(Modifier::BOLD),
-                        )
-                    } else {
-                        // Render text with syntax highlighting
-                        (ch.to_string(), token.token_type.style())
-                    };
-
-                    spans.push(Span::styled(display_ch, style));
-                    col += 1;
-                }
-            }
-
-            // Add trailing space for empty line handling
-            if line_content.is_empty() {
-                let pos = Position { row, col };
-                let (ch, style) = if pos == cursor.pos() {
-                    (
-                        PLAYER_CHAR.to_string(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    )
-                } else if enemies.contains(&pos) {
-                    (
-                        ENEMY_CHAR.to_string(),
-                        Style::default().add_modifier(Modifier::BOLD),
-                    )
-                } else {
-                    (" ".to_string(), Style::default())
-                };
-                spans.push(Span::styled(ch, style));
-            }
-
-            lines.push(Line::from(spans));
+    // Handle game elements rendering with a hierarchy
+    let lines = buffer_lines(buffer, viewport_line_start..end_row, |pos, ch, style| {
+        if let Some(effect) = effects.get(&pos) {
+            // Render effect
+            draw_effect(effect, now)
+        } else if pos == cursor.pos() {
+            // Render cursor
+            (
+                PLAYER_CHAR.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+        } else if ghost == Some(pos) {
+            // Render the ghost cursor under the player's
+            (PLAYER_CHAR.to_string(), Style::default().fg(GHOST_COLOR))
+        } else if enemies.get(&pos) == Some(&EnemyKind::Boss) {
+            // Render bosses over the text they hold, so its characters stay targetable
+            (
+                ch.to_string(),
+                style.bg(BOSS_BG_COLOR).add_modifier(Modifier::BOLD),
+            )
+        } else if let Some(kind) = enemies.get(&pos) {
+            // Render enemies
+            (kind.glyph().to_string(), enemy_style)
+        } else if let Some(kind) = powerups.get(&pos) {
+            // Render power-ups
+            (
+                kind.glyph().to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+        } else if projectiles.contains(&pos) {
+            // Render projectiles
+            (
+                PROJECTILE_CHAR.to_string(),
+                Style::default()
+                    .fg(PROJECTILE_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else if hint_path.contains(&pos) {
+            // Render hint ghost path
+            (ch.to_string(), style.bg(HINT_PATH_BG_COLOR))
+        } else if world.hazards().is_burning(pos.row) {
+            // Render lines on fire
+            (ch.to_string(), style.bg(FIRE_BG_COLOR))
+        } else {
+            // Render text with syntax highlighting
+            (ch.to_string(), style)
         }
-    }
+    });
 
     let paragraph =
         Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(GAME_TITLE));
@@ -98,33 +159,122 @@ pub fn render_world(
     f.render_widget(paragraph, area);
 }
 
+/// What the status bar shows of the round besides the world itself.
+pub struct StatusView<'a> {
+    pub stats: &'a RoundStats,
+    pub hint: Option<&'a Route>,
+    /// Why a motion was just rejected by the drill or a hint refused, if any
+    pub rejection: Option<&'a str>,
+    /// Score of the ghost being raced, if any
+    pub ghost_score: Option<usize>,
+}
+
 /// Renders the status bar at the bottom of the UI.
 pub fn render_status_bar<'a>(
     f: &mut Frame,
     game: &World,
+    status: StatusView,
     keys_iter: impl Iterator<Item = &'a KeyEvent>,
     area: Rect,
 ) {
+    let StatusView {
+        stats,
+        hint,
+        rejection,
+        ghost_score,
+    } = status;
     let cursor = game.cursor().pos();
     let recent_pressed = recent_pressed(keys_iter);
-    let status_text = format!(
-        "Score: {} | Position: {}:{} | Recent Keys: [{}] | {}",
+    let mut status_text = String::new();
+    if let Some(drill) = game.drill() {
+        status_text.push_str(&format!(
+            "Drill: {} | Errors: {} | ",
+            drill.name, stats.drill_errors
+        ));
+    }
+    status_text.push_str(&format!("Wave: {} | ", game.enemies().wave()));
+    for (kind, remaining) in game.powerups().active(game.clock().now()) {
+        status_text.push_str(&format!("{} {}s | ", kind, remaining.as_secs() + 1));
+    }
+    if let Some(boss) = game.enemies().iter().find(|e| e.kind() == EnemyKind::Boss) {
+        status_text.push_str(&format!("Boss HP: {} | ", boss.hit_points()));
+    }
+    status_text.push_str(&format!(
+        "Score: {} | Hints: {} | Position: {}:{} | Recent Keys: [{}] | ",
         game.score(),
+        stats.hints_used,
         cursor.row,
         cursor.col,
         recent_pressed,
-        STATUS_INSTRUCTIONS
-    );
+    ));
+    if let Some(ghost_score) = ghost_score {
+        let delta = game.score() as i64 - ghost_score as i64;
+        status_text.push_str(&format!("vs Ghost: {:+} | ", delta));
+    }
+    match (rejection, hint) {
+        (Some(rejection), _) => status_text.push_str(&format!("✗ {}", rejection)),
+        (None, Some(hint)) => {
+            status_text.push_str(&format!("Hint: {} ({} keys)", hint.keys(), hint.cost))
+        }
+        (None, None) => status_text.push_str(STATUS_INSTRUCTIONS),
+    }
+    let bg = if rejection.is_some() {
+        REJECTED_BG_COLOR
+    } else {
+        STATUS_BG_COLOR
+    };
 
     let status = Paragraph::new(status_text)
-        .style(Style::default().bg(STATUS_BG_COLOR).fg(STATUS_FG_COLOR))
+        .style(Style::default().bg(bg).fg(STATUS_FG_COLOR))
         .block(Block::default().borders(Borders::ALL));
 
     f.render_widget(status, area);
 }
 
+/// Renders recent score popups, newest at the bottom, in the top right corner of `area`.
+pub fn render_score_popups<'a>(
+    f: &mut Frame,
+    popups: impl ExactSizeIterator<Item = &'a String>,
+    area: Rect,
+) {
+    let count = popups.len() as u16;
+    if count == 0 {
+        return;
+    }
+
+    let lines: Vec<Line> = popups
+        .map(|text| {
+            Line::from(Span::styled(
+                text.clone(),
+                Style::default()
+                    .fg(SCORE_POPUP_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        })
+        .collect();
+    let width = lines.iter().map(|l| l.width() as u16).max().unwrap_or(0);
+
+    // Stay inside the borders of the game area
+    let popup_area = Rect {
+        x: area.right().saturating_sub(width + BORDER_LENGTH),
+        y: area.y + 1,
+        width: width.min(area.width.saturating_sub(BORDER_LENGTH)),
+        height: count.min(area.height.saturating_sub(BORDER_LENGTH)),
+    };
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Right),
+        popup_area,
+    );
+}
+
 /// Renders the file selection UI
-pub fn render_file_select(f: &mut Frame, input: &str, error: &Option<String>) {
+pub fn render_file_select(
+    f: &mut Frame,
+    input: &str,
+    difficulty: Difficulty,
+    spawn_rule: Option<SpawnRule>,
+    error: &Option<String>,
+) {
     let area = f.area();
     let dialog_area = centered_rect(FILE_SELECTION_SIZE.0, FILE_SELECTION_SIZE.1, area);
 
@@ -137,12 +287,27 @@ pub fn render_file_select(f: &mut Frame, input: &str, error: &Option<String>) {
         )),
         Line::from(""),
         Line::from(FILE_SELECTION_INSTRUCTION),
+        Line::from(FILE_SELECTION_OPTIONS),
         Line::from(""),
         Line::from(vec![
             Span::raw("Path: "),
             Span::styled(input, Style::default().fg(FILE_SELECTION_INPUT_COLOR)),
             Span::styled("_", Style::default().fg(Color::Gray)),
         ]),
+        Line::from(vec![
+            Span::raw("Difficulty: "),
+            Span::styled(
+                difficulty.to_string(),
+                Style::default().fg(FILE_SELECTION_INPUT_COLOR),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("Spawns: "),
+            Span::styled(
+                spawn_rule.map_or_else(|| "default".to_string(), |r| r.to_string()),
+                Style::default().fg(FILE_SELECTION_INPUT_COLOR),
+            ),
+        ]),
     ];
 
     if let Some(err) = error {
@@ -214,9 +379,619 @@ pub fn render_menu(f: &mut Frame, menu: &Menu) {
     f.render_widget(list, chunks[1]);
 }
 
+/// Renders the results of a round, with the high score table it was played for.
+pub fn render_results(f: &mut Frame, results: ResultsView) {
+    let area = centered_rect(RESULTS_SIZE.0, RESULTS_SIZE.1, f.area());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(MENU_LINE_COLOR))
+        .title(RESULTS_TITLE)
+        .title_bottom(RESULTS_INSTRUCTION);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Length(5), Constraint::Min(0)])
+        .split(inner);
+
+    let breakdown: Vec<String> = results
+        .points
+        .iter()
+        .map(|(reason, points)| format!("{} {}", reason, points))
+        .collect();
+    let rank = match results.rank {
+        Some(rank) => Span::styled(
+            format!("New high score: #{}", rank + 1),
+            Style::default()
+                .fg(MENU_SELECTED_COLOR)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
+    let summary = Paragraph::new(vec![
+        Line::from(format!(
+            "Score: {} | Keystrokes: {} | Accuracy: {}%",
+            results.score, results.keystrokes, results.accuracy
+        )),
+        Line::from(Span::styled(
+            breakdown.join(" | "),
+            Style::default().fg(SCORE_POPUP_COLOR),
+        )),
+        Line::from(rank),
+        Line::from(Span::styled(
+            score_table_label(results.key, &results.scores),
+            Style::default().fg(MENU_TITLE_COLOR),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(summary, chunks[0]);
+    f.render_widget(high_score_table(&results.scores, results.rank), chunks[1]);
+}
+
+/// Renders the high score tables, the list on the left and the picked table on the right.
+pub fn render_high_scores(f: &mut Frame, browser: &HighScoreBrowser, scores: &HighScores) {
+    let area = centered_rect(HIGH_SCORES_SIZE.0, HIGH_SCORES_SIZE.1, f.area());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(MENU_LINE_COLOR))
+        .title(HIGH_SCORES_TITLE)
+        .title_bottom(HIGH_SCORES_INSTRUCTION);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(HIGH_SCORES_LIST_WIDTH),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let mut items: Vec<ListItem> = browser
+        .keys()
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let (prefix, style) = if i == browser.selected_idx() {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(MENU_SELECTED_COLOR)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default())
+            };
+            let label = score_table_label(key, &scores.top(key));
+            ListItem::new(Line::from(Span::styled(
+                format!("{}{}", prefix, label),
+                style,
+            )))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new("No rounds played yet"));
+    }
+    f.render_widget(List::new(items), chunks[0]);
+
+    if let Some(key) = browser.selected_key() {
+        f.render_widget(high_score_table(&scores.top(key), None), chunks[1]);
+    }
+}
+
+/// Names a high score table after the file played, the mode and the difficulty.
+fn score_table_label(key: &ScoreKey, scores: &[&HighScore]) -> String {
+    // Only a hash of the path is kept in the key, the scores know the file
+    let file = match scores.first() {
+        Some(score) => std::path::Path::new(&score.file).file_name().map_or_else(
+            || score.file.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        None => key.source.clone(),
+    };
+    format!("{} · {} · {}", file, key.mode, key.difficulty)
+}
+
+/// Builds a high score table, best first, with the row at `highlight` stressed.
+fn high_score_table<'a>(scores: &[&HighScore], highlight: Option<usize>) -> Table<'a> {
+    let rows = scores.iter().enumerate().map(|(i, score)| {
+        let style = if Some(i) == highlight {
+            Style::default()
+                .fg(MENU_SELECTED_COLOR)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            format!("{}", i + 1),
+            score.score.to_string(),
+            format_date(score.date),
+            score.keystrokes.to_string(),
+            format!("{}%", score.accuracy),
+        ])
+        .style(style)
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Length(6),
+            Constraint::Length(8),
+        ],
+    )
+    .header(
+        Row::new(["#", "Score", "Date", "Keys", "Accuracy"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+}
+
+/// Renders the list of recorded replays.
+pub fn render_replay_select(f: &mut Frame, selector: &ReplaySelector) {
+    let area = centered_rect(REPLAY_SELECT_SIZE.0, REPLAY_SELECT_SIZE.1, f.area());
+
+    let mut items: Vec<ListItem> = selector
+        .files()
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            let (prefix, style) = if i == selector.selected_idx() {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(MENU_SELECTED_COLOR)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default())
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!("{}{}", prefix, name),
+                style,
+            )))
+        })
+        .collect();
+
+    if items.is_empty() {
+        items.push(ListItem::new(format!(
+            "No .{} files recorded yet",
+            REPLAY_EXTENSION
+        )));
+    }
+    if let Some(err) = selector.error() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            format!("❌ {}", err),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MENU_LINE_COLOR))
+            .title(REPLAY_SELECT_TITLE)
+            .title_bottom(REPLAY_SELECT_INSTRUCTION),
+    );
+    f.render_widget(list, area);
+}
+
+/// Renders the playback position, speed and controls of a replay.
+pub fn render_replay_bar(f: &mut Frame, replay: ReplayView, area: Rect) {
+    let clock = |d: Duration| format!("{:02}:{:02}", d.as_secs() / 60, d.as_secs() % 60);
+    let state = if replay.paused { "⏸" } else { "▶" };
+    let text = format!(
+        "REPLAY {} {} / {} | {}× | Final score: {} | {}",
+        state,
+        clock(replay.position),
+        clock(replay.duration),
+        replay.speed,
+        replay.score,
+        REPLAY_INSTRUCTIONS,
+    );
+
+    let bar = Paragraph::new(text)
+        .style(Style::default().bg(REPLAY_BAR_BG_COLOR).fg(STATUS_FG_COLOR))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(bar, area);
+}
+
+/// Renders the drill selection list with the motions each drill allows.
+pub fn render_drill_select(f: &mut Frame, selector: &DrillSelector) {
+    let area = centered_rect(DRILL_SELECT_SIZE.0, DRILL_SELECT_SIZE.1, f.area());
+
+    let items: Vec<ListItem> = DRILLS
+        .iter()
+        .enumerate()
+        .map(|(i, drill)| {
+            let (prefix, style) = if i == selector.selected_idx() {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(MENU_SELECTED_COLOR)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default())
+            };
+            let keys: String = drill.motions.iter().map(|k| k.key()).collect();
+            ListItem::new(vec![
+                Line::from(Span::styled(format!("{}{}", prefix, drill.name), style)),
+                Line::from(Span::styled(
+                    format!("    allowed: {} | enemies on: {}", keys, drill.spawn_rule),
+                    Style::default().fg(LESSON_LOCKED_COLOR),
+                )),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MENU_LINE_COLOR))
+            .title(DRILL_SELECT_TITLE)
+            .title_bottom(DRILL_SELECT_INSTRUCTION),
+    );
+
+    f.render_widget(list, area);
+}
+
+/// Renders the list of challenge files.
+pub fn render_challenge_select(f: &mut Frame, selector: &ChallengeSelector) {
+    let area = centered_rect(CHALLENGE_SELECT_SIZE.0, CHALLENGE_SELECT_SIZE.1, f.area());
+
+    let mut items: Vec<ListItem> = selector
+        .files()
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            let (prefix, style) = if i == selector.selected_idx() {
+                (
+                    "> ",
+                    Style::default()
+                        .fg(MENU_SELECTED_COLOR)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default())
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!("{}{}", prefix, name),
+                style,
+            )))
+        })
+        .collect();
+
+    if items.is_empty() {
+        items.push(ListItem::new(format!(
+            "No .{} files in '{}'",
+            CHALLENGE_EXTENSION, CHALLENGE_DIR
+        )));
+    }
+    if let Some(err) = selector.error() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            format!("❌ {}", err),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MENU_LINE_COLOR))
+            .title(CHALLENGE_SELECT_TITLE)
+            .title_bottom(CHALLENGE_SELECT_INSTRUCTION),
+    );
+
+    f.render_widget(list, area);
+}
+
+/// Renders a challenge: the buffer being edited next to the remaining diff against the goal.
+pub fn render_challenge<'a>(
+    f: &mut Frame,
+    run: &ChallengeRun,
+    inserting: bool,
+    keys_iter: impl Iterator<Item = &'a KeyEvent>,
+) {
+    let chunks = Layout::default()
+        .constraints([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+        .split(f.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+
+    // Rows of the buffer already matching the goal
+    let diff = run.diff();
+    let mut matched = vec![];
+    let mut row = 0;
+    for line in &diff {
+        match line {
+            DiffLine::Same(_) => {
+                matched.push(row);
+                row += 1;
+            }
+            DiffLine::Removed(_) => row += 1,
+            DiffLine::Added(_) => {}
+        }
+    }
+
+    let buffer = run.buffer();
+    let cursor = run.cursor().pos();
+    let mut lines = buffer_lines(buffer, 0..buffer.rows(), |pos, ch, style| {
+        let style = if matched.contains(&pos.row) {
+            style.bg(CHALLENGE_MATCH_BG_COLOR)
+        } else {
+            style
+        };
+        if pos == cursor {
+            (
+                PLAYER_CHAR.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (ch.to_string(), style)
+        }
+    });
+
+    // The cursor can sit right after the end of the line while inserting
+    if let Some(line) = lines.get_mut(cursor.row)
+        && cursor.col >= buffer.get_line_len(cursor.row)
+        && !buffer.is_empty_line(&cursor)
+    {
+        line.spans.push(Span::styled(
+            PLAYER_CHAR,
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let editor = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(run.challenge().title.as_str()),
+    );
+    f.render_widget(editor, columns[0]);
+
+    // Remaining differences
+    let remaining = diff
+        .iter()
+        .filter(|l| !matches!(l, DiffLine::Same(_)))
+        .count();
+    let diff_lines: Vec<Line> = diff
+        .iter()
+        .map(|line| match line {
+            DiffLine::Same(text) => Line::from(Span::styled(
+                format!("  {}", text),
+                Style::default().fg(DIFF_SAME_COLOR),
+            )),
+            DiffLine::Removed(text) => Line::from(Span::styled(
+                format!("- {}", text),
+                Style::default().fg(DIFF_REMOVED_COLOR),
+            )),
+            DiffLine::Added(text) => Line::from(Span::styled(
+                format!("+ {}", text),
+                Style::default().fg(DIFF_ADDED_COLOR),
+            )),
+        })
+        .collect();
+    let title = format!("{} ({} left)", CHALLENGE_DIFF_TITLE, remaining);
+    let panel =
+        Paragraph::new(diff_lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(panel, columns[1]);
+
+    // Status bar
+    let elapsed = run.elapsed().as_secs_f32();
+    let status_text = if run.is_solved() {
+        format!(
+            "Solved in {} keys and {:.1}s! | Press ':q' to go back",
+            run.keystrokes(),
+            elapsed
+        )
+    } else {
+        format!(
+            "{}Keys: {} | Time: {:.1}s | Recent Keys: [{}] | Press ':q' to go back",
+            if inserting { "-- INSERT -- | " } else { "" },
+            run.keystrokes(),
+            elapsed,
+            recent_pressed(keys_iter)
+        )
+    };
+    let status = Paragraph::new(status_text)
+        .style(Style::default().bg(STATUS_BG_COLOR).fg(STATUS_FG_COLOR))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[1]);
+}
+
+/// Renders the lesson selection list, marking locked and completed lessons.
+pub fn render_lesson_select(f: &mut Frame, selector: &LessonSelector, progress: &LessonProgress) {
+    let area = centered_rect(LESSON_SELECT_SIZE.0, LESSON_SELECT_SIZE.1, f.area());
+
+    let items: Vec<ListItem> = LESSONS
+        .iter()
+        .enumerate()
+        .map(|(i, lesson)| {
+            let prefix = if i == selector.selected_idx() {
+                "> "
+            } else {
+                "  "
+            };
+            let status = match progress.grade(lesson.id) {
+                Some(grade) => format!("[{}]", grade),
+                None if progress.is_unlocked(i) => String::new(),
+                None => "[locked]".to_string(),
+            };
+            let style = if !progress.is_unlocked(i) {
+                Style::default().fg(LESSON_LOCKED_COLOR)
+            } else if i == selector.selected_idx() {
+                Style::default()
+                    .fg(MENU_SELECTED_COLOR)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!("{}{}. {} {}", prefix, i + 1, lesson.title, status),
+                style,
+            )))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(MENU_LINE_COLOR))
+            .title(LESSON_SELECT_TITLE)
+            .title_bottom(LESSON_SELECT_INSTRUCTION),
+    );
+
+    f.render_widget(list, area);
+}
+
+/// Renders a lesson: the exercise buffer, the explanation panel and a status bar.
+pub fn render_lesson<'a>(
+    f: &mut Frame,
+    session: LessonView,
+    keys_iter: impl Iterator<Item = &'a KeyEvent>,
+) {
+    let LessonView {
+        index,
+        lesson,
+        phase,
+        exercise,
+        demo_finished,
+    } = session;
+
+    let chunks = Layout::default()
+        .constraints([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+        .split(f.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(LESSON_PANEL_WIDTH)])
+        .split(chunks[0]);
+
+    // Exercise buffer with the cursor and the next target
+    let buffer = exercise.buffer();
+    let cursor = exercise.cursor().pos();
+    let target = exercise.target();
+    let lines = buffer_lines(buffer, 0..buffer.rows(), |pos, ch, style| {
+        if pos == cursor {
+            (
+                PLAYER_CHAR.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+        } else if Some(pos) == target {
+            (
+                ch.to_string(),
+                style
+                    .bg(LESSON_TARGET_BG_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (ch.to_string(), style)
+        }
+    });
+    let title = format!("Lesson {}: {}", index + 1, lesson.title);
+    let editor = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(editor, columns[0]);
+
+    // Explanation panel
+    let mut text: Vec<Line> = lesson.explanation.iter().map(|l| Line::from(*l)).collect();
+    text.push(Line::from(""));
+    let next_step = match phase {
+        LessonPhase::Explanation => "Press Enter to watch a demo.".to_string(),
+        LessonPhase::Demo if demo_finished => "Press Enter to try it yourself.".to_string(),
+        LessonPhase::Demo => format!("Demo: {}", lesson.demo),
+        LessonPhase::Exercise => "Reach the highlighted characters in order.".to_string(),
+        LessonPhase::Result(grade) => format!("Done: {}!", grade),
+    };
+    text.push(Line::from(Span::styled(
+        next_step,
+        Style::default()
+            .fg(MENU_TITLE_COLOR)
+            .add_modifier(Modifier::BOLD),
+    )));
+    let panel = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(LESSON_PANEL_TITLE),
+    );
+    f.render_widget(panel, columns[1]);
+
+    // Status bar
+    let (reached, total) = exercise.progress();
+    let status_text = match phase {
+        LessonPhase::Explanation | LessonPhase::Demo => format!(
+            "Recent Keys: [{}] | Enter to continue, Esc to go back",
+            recent_pressed(keys_iter)
+        ),
+        LessonPhase::Exercise => format!(
+            "Targets: {}/{} | Keys: {} | Par: {} | Recent Keys: [{}] | Esc to go back",
+            reached,
+            total,
+            exercise.keystrokes(),
+            exercise.par(),
+            recent_pressed(keys_iter)
+        ),
+        LessonPhase::Result(grade) => format!(
+            "Grade: {} | Keys: {} | Par: {} | Enter for next lesson, 'r' to retry, Esc to go back",
+            grade,
+            exercise.keystrokes(),
+            exercise.par()
+        ),
+    };
+    let status = Paragraph::new(status_text)
+        .style(Style::default().bg(STATUS_BG_COLOR).fg(STATUS_FG_COLOR))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[1]);
+}
+
+/// Builds the lines of `rows` in the buffer with syntax highlighting,
+/// letting `cell` decide how each position is drawn.
+fn buffer_lines(
+    buffer: &Buffer,
+    rows: Range<usize>,
+    cell: impl Fn(Position, char, Style) -> (String, Style),
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+
+    for row in rows {
+        if let Some(line_content) = buffer.get_line(row) {
+            let mut spans = vec![];
+
+            // Tokenize line for syntax highlighting
+            let tokens = tokenize_line(line_content);
+            let mut col = 0;
+
+            // Draw each token with appropriate style
+            for token in tokens {
+                for ch in token.text.chars() {
+                    let (display_ch, style) =
+                        cell(Position { row, col }, ch, syntax::style(token.token_type));
+                    spans.push(Span::styled(display_ch, style));
+                    col += 1;
+                }
+            }
+
+            // Add trailing space for empty line handling
+            if line_content.is_empty() {
+                let (ch, style) = cell(Position { row, col }, ' ', Style::default());
+                spans.push(Span::styled(ch, style));
+            }
+
+            lines.push(Line::from(spans));
+        }
+    }
+
+    lines
+}
+
 /// Draws a visual effect based on its type and elapsed time.
-fn draw_effect(effect: &Effect) -> (String, Style) {
-    let elapsed = effect.percentage_elapsed();
+fn draw_effect(effect: &Effect, now: Instant) -> (String, Style) {
+    let elapsed = effect.percentage_elapsed(now);
 
     let (ch, color) = match effect.ty {
         EffectType::Collision => {
@@ -245,6 +1020,35 @@ fn draw_effect(effect: &Effect) -> (String, Style) {
             };
             (ch, Color::Rgb(brightness, brightness, brightness))
         }
+        EffectType::GhostTrailing => {
+            // Fainter than the player's trail, so the two never get confused
+            let brightness = ((1.0 - elapsed) * GHOST_TRAIL_BRIGHTNESS) as u8;
+            ("░", Color::Rgb(brightness, brightness, brightness))
+        }
+        EffectType::GhostKill => ("×", GHOST_COLOR),
+        EffectType::Sparkle => {
+            if elapsed < 0.33 {
+                ("✦", Color::LightYellow)
+            } else if elapsed < 0.66 {
+                ("✧", Color::Yellow)
+            } else {
+                ("·", Color::DarkGray)
+            }
+        }
+        EffectType::Burn => {
+            if elapsed < 0.5 {
+                ("▲", Color::LightRed)
+            } else {
+                ("▲", Color::Yellow)
+            }
+        }
+        EffectType::Deflect => {
+            if elapsed < 0.5 {
+                ("✕", Color::Red)
+            } else {
+                ("✕", Color::DarkGray)
+            }
+        }
     };
 
     (
@@ -0,0 +1,85 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Extension of replay files.
+pub const REPLAY_EXTENSION: &str = "replay";
+
+/// Actions as a result of replay selection input.
+pub enum ReplaySelectAction {
+    Play(PathBuf),
+    /// Race against the run recorded in the replay
+    Race(PathBuf),
+    Cancel,
+    Noop,
+}
+
+/// Manages the list of recorded replays, newest first.
+#[derive(Default)]
+pub struct ReplaySelector {
+    files: Vec<PathBuf>,
+    selected: usize,
+    error: Option<String>,
+}
+
+impl ReplaySelector {
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn selected_idx(&self) -> usize {
+        self.selected
+    }
+
+    pub fn error(&self) -> &Option<String> {
+        &self.error
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Lists the replay files in `dir` again.
+    pub fn refresh(&mut self, dir: Option<&Path>) {
+        self.files = dir
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|e| e == REPLAY_EXTENSION))
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Replays are named after when they were recorded
+        self.files.sort_by(|a, b| b.cmp(a));
+        self.selected = self.selected.min(self.files.len().saturating_sub(1));
+        self.error = None;
+    }
+
+    /// Handles a key event in the replay list.
+    pub fn handle_key(&mut self, key: KeyEvent) -> ReplaySelectAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.files.len().saturating_sub(1));
+                ReplaySelectAction::Noop
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                ReplaySelectAction::Noop
+            }
+            KeyCode::Enter => match self.files.get(self.selected) {
+                Some(path) => ReplaySelectAction::Play(path.clone()),
+                None => ReplaySelectAction::Noop,
+            },
+            KeyCode::Char('r') => match self.files.get(self.selected) {
+                Some(path) => ReplaySelectAction::Race(path.clone()),
+                None => ReplaySelectAction::Noop,
+            },
+            KeyCode::Esc => ReplaySelectAction::Cancel,
+            _ => ReplaySelectAction::Noop,
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use std::{fmt::Write, path::Path};
+
+use ratatui::{
+    Frame, Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Modifier},
+    text::Span,
+};
+
+use crate::{
+    domain::{RoundStats, World},
+    tui::{UiManager, theme::*},
+};
+
+/// Formats a frame can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// Plain text, without colours
+    Text,
+    /// Text with ANSI colour escapes, for terminals and chat
+    Ansi,
+    /// A standalone HTML page
+    Html,
+    Svg,
+}
+
+impl ScreenshotFormat {
+    /// Picks the format from the extension of `path`, plain text by default.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ansi") => Self::Ansi,
+            Some("html" | "htm") => Self::Html,
+            Some("svg") => Self::Svg,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Renders `world` as the game would draw it on a `width` × `height` terminal,
+/// with the status bar showing `stats`, in `format`.
+pub fn screenshot(
+    world: &World,
+    stats: &RoundStats,
+    width: u16,
+    height: u16,
+    format: ScreenshotFormat,
+) -> String {
+    UiManager::new(world.clock().clone()).screenshot(
+        world,
+        stats,
+        std::iter::empty(),
+        width,
+        height,
+        format,
+    )
+}
+
+/// Draws a frame of `width` × `height` cells off screen.
+pub fn capture(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Buffer {
+    // Drawing on the test backend only touches memory, it cannot fail
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+    terminal.draw(draw).expect("test backend never fails");
+    terminal.backend().buffer().clone()
+}
+
+/// Exports a drawn frame in `format`.
+pub fn export(buffer: &Buffer, format: ScreenshotFormat) -> String {
+    let rows = runs(buffer);
+    match format {
+        ScreenshotFormat::Text => export_text(&rows),
+        ScreenshotFormat::Ansi => export_ansi(&rows),
+        ScreenshotFormat::Html => export_html(&rows),
+        ScreenshotFormat::Svg => export_svg(&rows, buffer.area.width, buffer.area.height),
+    }
+}
+
+/// Cells of a row drawn alike, merged together.
+struct Run {
+    /// Column of the first cell
+    col: u16,
+    /// Number of cells covered, wide glyphs counting twice
+    width: u16,
+    text: String,
+    fg: Option<(u8, u8, u8)>,
+    bg: Option<(u8, u8, u8)>,
+    bold: bool,
+}
+
+/// Splits each row of `buffer` into runs of cells with the same style.
+fn runs(buffer: &Buffer) -> Vec<Vec<Run>> {
+    let area = buffer.area;
+    let mut rows = vec![];
+
+    for y in area.top()..area.bottom() {
+        let mut row: Vec<Run> = vec![];
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // Cells hidden behind a wide glyph are not drawn
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            let width = Span::raw(cell.symbol()).width().max(1) as u16;
+            skip = width - 1;
+
+            let (fg, bg) = (rgb(cell.fg), rgb(cell.bg));
+            let bold = cell.modifier.contains(Modifier::BOLD);
+            match row.last_mut() {
+                Some(run) if run.fg == fg && run.bg == bg && run.bold == bold => {
+                    run.text.push_str(cell.symbol());
+                    run.width += width;
+                }
+                _ => row.push(Run {
+                    col: x - area.left(),
+                    width,
+                    text: cell.symbol().to_string(),
+                    fg,
+                    bg,
+                    bold,
+                }),
+            }
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+fn export_text(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let line: String = row.iter().map(|run| run.text.as_str()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn export_ansi(rows: &[Vec<Run>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        for run in row {
+            out.push_str("\x1b[0");
+            if run.bold {
+                out.push_str(";1");
+            }
+            if let Some((r, g, b)) = run.fg {
+                let _ = write!(out, ";38;2;{};{};{}", r, g, b);
+            }
+            if let Some((r, g, b)) = run.bg {
+                let _ = write!(out, ";48;2;{};{};{}", r, g, b);
+            }
+            out.push('m');
+            out.push_str(&run.text);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn export_html(rows: &[Vec<Run>]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+         <body style=\"background:{bg}\">\n<pre style=\"font-family:monospace;color:{fg};background:{bg};line-height:1.2\">\n",
+        escape(GAME_TITLE),
+        fg = hex(SCREENSHOT_FG),
+        bg = hex(SCREENSHOT_BG),
+    );
+    for row in rows {
+        for run in row {
+            let mut style = vec![];
+            if let Some(fg) = run.fg {
+                style.push(format!("color:{}", hex(fg)));
+            }
+            if let Some(bg) = run.bg {
+                style.push(format!("background:{}", hex(bg)));
+            }
+            if run.bold {
+                style.push("font-weight:bold".to_string());
+            }
+
+            if style.is_empty() {
+                out.push_str(&escape(&run.text));
+            } else {
+                let _ = write!(
+                    out,
+                    "<span style=\"{}\">{}</span>",
+                    style.join(";"),
+                    escape(&run.text)
+                );
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn export_svg(rows: &[Vec<Run>], width: u16, height: u16) -> String {
+    let (cell_width, cell_height) = SCREENSHOT_CELL_SIZE;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"monospace\" font-size=\"{size}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{bg}\"/>\n",
+        w = width as f32 * cell_width,
+        h = height as f32 * cell_height,
+        size = SCREENSHOT_FONT_SIZE,
+        bg = hex(SCREENSHOT_BG),
+    );
+
+    for (y, row) in rows.iter().enumerate() {
+        let top = y as f32 * cell_height;
+        for run in row {
+            let left = run.col as f32 * cell_width;
+            if let Some(bg) = run.bg {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                    left,
+                    top,
+                    run.width as f32 * cell_width,
+                    cell_height,
+                    hex(bg)
+                );
+            }
+            if run.text.trim().is_empty() {
+                continue;
+            }
+            let weight = if run.bold {
+                " font-weight=\"bold\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" fill=\"{}\"{} xml:space=\"preserve\">{}</text>",
+                left,
+                // Text sits on its baseline, near the bottom of the cell
+                top + cell_height * 0.8,
+                hex(run.fg.unwrap_or(SCREENSHOT_FG)),
+                weight,
+                escape(&run.text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Converts a terminal colour to RGB, None for the terminal's default.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (128, 0, 0),
+        (0, 128, 0),
+        (128, 128, 0),
+        (0, 0, 128),
+        (128, 0, 128),
+        (0, 128, 128),
+        (192, 192, 192),
+        (128, 128, 128),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (0, 0, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(i) => i,
+    };
+
+    Some(match index {
+        0..16 => ANSI[index as usize],
+        // 6×6×6 colour cube
+        16..232 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = index - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        // Grayscale ramp
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    })
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Escapes text for HTML and SVG.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod screenshot_tests {
+    use super::*;
+    use crate::domain::GameConfig;
+    use ratatui::{style::Style, widgets::Paragraph};
+
+    fn frame() -> Buffer {
+        capture(12, 2, |f| {
+            let text = ratatui::text::Line::from(vec![
+                Span::styled("fn", Style::default().fg(Color::Rgb(242, 195, 92))),
+                Span::raw(" <a>"),
+                Span::styled("👾", Style::default().add_modifier(Modifier::BOLD)),
+            ]);
+            f.render_widget(Paragraph::new(text), f.area());
+        })
+    }
+
+    #[test]
+    fn test_export_keeps_glyphs_and_colours() {
+        let buffer = frame();
+
+        assert_eq!(export(&buffer, ScreenshotFormat::Text), "fn <a>👾\n\n");
+        let ansi = export(&buffer, ScreenshotFormat::Ansi);
+        assert!(ansi.starts_with("\x1b[0;38;2;242;195;92mfn\x1b[0m <a>\x1b[0;1m👾"));
+        let html = export(&buffer, ScreenshotFormat::Html);
+        assert!(html.contains("<span style=\"color:#f2c35c\">fn</span> &lt;a&gt;"));
+        let svg = export(&buffer, ScreenshotFormat::Svg);
+        assert!(svg.contains("fill=\"#f2c35c\" xml:space=\"preserve\">fn</text>"));
+        assert!(svg.contains("font-weight=\"bold\" xml:space=\"preserve\">👾</text>"));
+    }
+
+    #[test]
+    fn test_screenshot_of_world() {
+        let world = World::new(GameConfig {
+            seed: Some(5),
+            ..GameConfig::default()
+        });
+        let text = screenshot(
+            &world,
+            &RoundStats::default(),
+            80,
+            20,
+            ScreenshotFormat::Text,
+        );
+
+        assert_eq!(text.lines().count(), 20);
+        assert!(text.contains(PLAYER_CHAR));
+        assert!(text.contains("Score: 0"));
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ScreenshotFormat::from_path(Path::new("a.svg")),
+            ScreenshotFormat::Svg
+        );
+        assert_eq!(
+            ScreenshotFormat::from_path(Path::new("a.htm")),
+            ScreenshotFormat::Html
+        );
+        assert_eq!(
+            ScreenshotFormat::from_path(Path::new("a")),
+            ScreenshotFormat::Text
+        );
+    }
+}
@@ -6,13 +6,30 @@ pub const GAME_TITLE: &str = "👾 HJKL: Code Invaders 👾";
 pub const MENU_TITLE: &str = "Menu";
 pub const FILE_SELECTION_TITLE: &str = "Select Rust File";
 pub const FILE_SELECTION_INSTRUCTION: &str =
-    "Enter path to .rs file | Ctrl+R for random | ESC to go back";
-pub const STATUS_INSTRUCTIONS: &str = "Press ':q' to quit, ':n' for new round";
+    "Enter path to .rs or .scenario file | Ctrl+R for random | ESC to go back";
+pub const FILE_SELECTION_OPTIONS: &str = "Tab for difficulty | Shift+Tab for where enemies spawn";
+pub const DRILL_SELECT_TITLE: &str = "Drills";
+pub const DRILL_SELECT_INSTRUCTION: &str = " Enter to pick a file | Esc to go back ";
+pub const CHALLENGE_SELECT_TITLE: &str = "Challenges";
+pub const CHALLENGE_SELECT_INSTRUCTION: &str = " Enter to start | Esc to go back ";
+pub const CHALLENGE_DIFF_TITLE: &str = "Goal diff";
+pub const LESSON_SELECT_TITLE: &str = "Lessons";
+pub const LESSON_SELECT_INSTRUCTION: &str = " Enter to start | Esc to go back ";
+pub const LESSON_PANEL_TITLE: &str = "How it works";
+pub const REPLAY_SELECT_TITLE: &str = "Replays";
+pub const REPLAY_SELECT_INSTRUCTION: &str = " Enter to play | r to race | Esc to go back ";
+pub const REPLAY_INSTRUCTIONS: &str = "Space pause | -/+ speed | h/l seek 5s | q back";
+pub const HIGH_SCORES_TITLE: &str = "High Scores";
+pub const HIGH_SCORES_INSTRUCTION: &str = " j/k to pick a table | Esc to go back ";
+pub const RESULTS_TITLE: &str = "Round over";
+pub const RESULTS_INSTRUCTION: &str = " Enter to continue ";
+pub const STATUS_INSTRUCTIONS: &str = "Press ':q' to quit, ':n' for new round, F1 for a hint";
 
 pub const PLAYER_CHAR: &str = "▓";
-pub const ENEMY_CHAR: &str = "👾";
+pub const PROJECTILE_CHAR: &str = "•";
 
 pub const STATUS_BAR_HEIGHT: u16 = 3;
+pub const REPLAY_BAR_HEIGHT: u16 = 3;
 pub const VIEWPORT_PADDING: usize = 3;
 pub const BORDER_LENGTH: u16 = 2; // 1 for top border + 1 for bottom border
 
@@ -22,12 +39,48 @@ pub const MENU_TITLE_COLOR: Color = Color::Cyan;
 pub const MENU_SELECTED_COLOR: Color = Color::Cyan;
 pub const MENU_LINE_COLOR: Color = Color::White;
 pub const FILE_SELECTION_INPUT_COLOR: Color = Color::Cyan;
+pub const LESSON_TARGET_BG_COLOR: Color = Color::Rgb(150, 60, 60);
+pub const LESSON_LOCKED_COLOR: Color = Color::DarkGray;
+pub const CHALLENGE_MATCH_BG_COLOR: Color = Color::Rgb(30, 60, 40);
+pub const DIFF_SAME_COLOR: Color = Color::DarkGray;
+pub const DIFF_REMOVED_COLOR: Color = Color::Red;
+pub const DIFF_ADDED_COLOR: Color = Color::Green;
+pub const REJECTED_BG_COLOR: Color = Color::Red;
+pub const HINT_PATH_BG_COLOR: Color = Color::Rgb(60, 70, 110);
+pub const BOSS_BG_COLOR: Color = Color::Rgb(120, 20, 40);
+pub const FIRE_BG_COLOR: Color = Color::Rgb(110, 45, 0);
+pub const PROJECTILE_COLOR: Color = Color::LightRed;
+pub const FROZEN_ENEMY_COLOR: Color = Color::LightCyan;
+pub const SCORE_POPUP_COLOR: Color = Color::LightYellow;
+pub const REPLAY_BAR_BG_COLOR: Color = Color::Rgb(40, 40, 90);
+pub const GHOST_COLOR: Color = Color::Rgb(100, 100, 130);
+pub const GHOST_TRAIL_BRIGHTNESS: f32 = 110.0;
+// Default text and background of exported screenshots, as RGB
+pub const SCREENSHOT_FG: (u8, u8, u8) = (208, 208, 208);
+pub const SCREENSHOT_BG: (u8, u8, u8) = (30, 30, 30);
 
 pub const MENU_SIZE: (u16, u16) = (60, 12); // width, height
-pub const FILE_SELECTION_SIZE: (u16, u16) = (70, 12); // width, height
+pub const FILE_SELECTION_SIZE: (u16, u16) = (100, 12); // width, height
+pub const DRILL_SELECT_SIZE: (u16, u16) = (60, 12); // width, height
+pub const CHALLENGE_SELECT_SIZE: (u16, u16) = (70, 14); // width, height
+pub const LESSON_SELECT_SIZE: (u16, u16) = (60, 12); // width, height
+pub const REPLAY_SELECT_SIZE: (u16, u16) = (70, 14); // width, height
+pub const HIGH_SCORES_SIZE: (u16, u16) = (100, 16); // width, height
+pub const RESULTS_SIZE: (u16, u16) = (64, 20); // width, height
+pub const HIGH_SCORES_LIST_WIDTH: u16 = 44;
+pub const LESSON_PANEL_WIDTH: u16 = 44;
+pub const SCREENSHOT_CELL_SIZE: (f32, f32) = (9.0, 18.0); // width, height in pixels
+pub const SCREENSHOT_FONT_SIZE: u16 = 15;
 
 pub const COLLISION_EFFECT_DURATION: Duration = Duration::from_millis(200);
 pub const TRAILING_EFFECT_DURATION: Duration = Duration::from_millis(200);
+pub const GHOST_KILL_EFFECT_DURATION: Duration = Duration::from_millis(1500);
+pub const DEFLECT_EFFECT_DURATION: Duration = Duration::from_millis(300);
+pub const BURN_EFFECT_DURATION: Duration = Duration::from_millis(400);
+pub const SPARKLE_EFFECT_DURATION: Duration = Duration::from_millis(500);
+pub const SCORE_POPUP_DURATION: Duration = Duration::from_millis(1500);
+pub const MAX_SCORE_POPUPS: usize = 4;
+pub const REJECTED_FEEDBACK_DURATION: Duration = Duration::from_millis(800);
 
 pub const SYNTAX_KEYWORD_COLOR: Color = Color::Rgb(242, 195, 92);
 pub const SYNTAX_TYPE_COLOR: Color = Color::Rgb(166, 123, 64);
@@ -1,22 +1,37 @@
 use crate::{
-    domain::{GameEvent, World},
+    domain::{
+        ChallengeRun, Difficulty, EventSubscriber, GameEvent, HighScores, LessonProgress,
+        RoundStats, Route, SharedClock, SharedSubscriber, SystemClock, World, enemies::SpawnRule,
+    },
     tui::{
-        Effect, Effects, FileSelectAction, FileSelector,
+        ChallengeSelectAction, ChallengeSelector, DrillSelectAction, DrillSelector, Effects,
+        FileSelectAction, FileSelector, GhostTrail, GhostView, HighScoreBrowser, HighScoresAction,
+        LessonSelectAction, LessonSelector, LessonView, ReplaySelectAction, ReplaySelector,
+        ReplayView, ResultsView, ScreenshotFormat, StatusView,
         menu::{Menu, MenuAction},
-        renderer,
-        theme::STATUS_BAR_HEIGHT,
+        renderer, screenshot,
+        theme::{
+            MAX_SCORE_POPUPS, REJECTED_FEEDBACK_DURATION, REPLAY_BAR_HEIGHT, SCORE_POPUP_DURATION,
+            STATUS_BAR_HEIGHT,
+        },
         viewport::Viewport,
     },
 };
 use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
 };
+use std::{cell::RefCell, collections::VecDeque, path::Path, rc::Rc, time::Instant};
 
 /// Actions that can be taken in the UI.
 pub enum UiAction {
     StartGame,
+    Drills,
+    Challenges,
+    Lessons,
+    Replays,
+    HighScores,
     Noop,
     Quit,
 }
@@ -25,32 +40,79 @@ pub enum UiAction {
 pub struct UiManager {
     menu: Menu,
     viewport: Viewport,
-    effects: Effects,
+    /// Effects of the round, following its events
+    effects: Rc<RefCell<Effects>>,
+    /// Hint, popups and drill feedback of the round, following its events
+    feedback: Rc<RefCell<Feedback>>,
     file_selector: FileSelector,
+    lesson_selector: LessonSelector,
+    drill_selector: DrillSelector,
+    challenge_selector: ChallengeSelector,
+    replay_selector: ReplaySelector,
+    high_score_browser: HighScoreBrowser,
+    /// Source of time for effects and feedback
+    clock: SharedClock,
 }
 
 impl Default for UiManager {
     fn default() -> Self {
+        Self::new(Rc::new(SystemClock))
+    }
+}
+
+impl UiManager {
+    /// Creates a UI manager timing its effects with `clock`.
+    pub fn new(clock: SharedClock) -> Self {
         Self {
             menu: Menu::default(),
             viewport: Viewport::default(),
-            effects: Effects::default(),
+            effects: Rc::default(),
+            feedback: Rc::default(),
             file_selector: FileSelector::new("src/main.rs"),
+            lesson_selector: LessonSelector::default(),
+            drill_selector: DrillSelector::default(),
+            challenge_selector: ChallengeSelector::default(),
+            replay_selector: ReplaySelector::default(),
+            high_score_browser: HighScoreBrowser::default(),
+            clock,
         }
     }
-}
 
-impl UiManager {
+    /// Times effects and feedback with `clock`, e.g. the clock of a new round.
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     pub fn reset(&mut self) {
         self.menu = Menu::default();
         self.viewport = Viewport::default();
-        self.effects = Effects::default();
+        // Cleared in place, the round keeps handing them its events
+        *self.effects.borrow_mut() = Effects::default();
+        *self.feedback.borrow_mut() = Feedback::default();
         self.file_selector.reset("src/main.rs");
     }
 
+    /// Returns the parts of the UI that follow the events of a round, to subscribe to it.
+    pub fn round_subscribers(&self) -> [SharedSubscriber; 2] {
+        [self.effects.clone(), self.feedback.clone()]
+    }
+
+    /// Returns a subscriber drawing a trail behind a raced ghost, to subscribe to its round.
+    pub fn ghost_subscriber(&self) -> SharedSubscriber {
+        Rc::new(RefCell::new(GhostTrail::new(
+            self.effects.clone(),
+            self.clock.clone(),
+        )))
+    }
+
     pub fn handle_menu_key(&mut self, key: KeyEvent) -> UiAction {
         match self.menu.handle_key(key) {
             MenuAction::Start => UiAction::StartGame,
+            MenuAction::Drill => UiAction::Drills,
+            MenuAction::Challenges => UiAction::Challenges,
+            MenuAction::Lessons => UiAction::Lessons,
+            MenuAction::Replays => UiAction::Replays,
+            MenuAction::HighScores => UiAction::HighScores,
             MenuAction::Quit => UiAction::Quit,
             MenuAction::Noop => UiAction::Noop,
         }
@@ -60,27 +122,215 @@ impl UiManager {
         self.file_selector.handle_key(key)
     }
 
+    /// Lists the high score tables again.
+    pub fn refresh_high_scores(&mut self, scores: &HighScores) {
+        self.high_score_browser.refresh(scores);
+    }
+
+    pub fn handle_high_scores_key(&mut self, key: KeyEvent) -> HighScoresAction {
+        self.high_score_browser.handle_key(key)
+    }
+
+    /// Returns the difficulty picked for the next round.
+    pub fn difficulty(&self) -> Difficulty {
+        self.file_selector.difficulty()
+    }
+
+    /// Returns the spawn rule picked for the next round, if any.
+    pub fn spawn_rule(&self) -> Option<SpawnRule> {
+        self.file_selector.spawn_rule()
+    }
+
+    pub fn set_file_error(&mut self, error: String) {
+        self.file_selector.set_error(error);
+    }
+
+    pub fn handle_drill_select_key(&mut self, key: KeyEvent) -> DrillSelectAction {
+        self.drill_selector.handle_key(key)
+    }
+
+    pub fn handle_challenge_select_key(&mut self, key: KeyEvent) -> ChallengeSelectAction {
+        self.challenge_selector.handle_key(key)
+    }
+
+    pub fn handle_replay_select_key(&mut self, key: KeyEvent) -> ReplaySelectAction {
+        self.replay_selector.handle_key(key)
+    }
+
+    /// Lists the replays recorded in `dir` again.
+    pub fn refresh_replays(&mut self, dir: Option<&Path>) {
+        self.replay_selector.refresh(dir);
+    }
+
+    /// Shows an error on the replay selection screen, e.g. for a malformed file.
+    pub fn set_replay_error(&mut self, error: String) {
+        self.replay_selector.set_error(error);
+    }
+
+    /// Lists the available challenge files again.
+    pub fn refresh_challenges(&mut self) {
+        self.challenge_selector.refresh();
+    }
+
+    /// Shows an error on the challenge selection screen, e.g. for a malformed file.
+    pub fn set_challenge_error(&mut self, error: String) {
+        self.challenge_selector.set_error(error);
+    }
+
+    pub fn handle_lesson_select_key(
+        &mut self,
+        key: KeyEvent,
+        progress: &LessonProgress,
+    ) -> LessonSelectAction {
+        self.lesson_selector.handle_key(key, progress)
+    }
+
+    /// Moves the lesson selection, e.g. to the lesson played last.
+    pub fn select_lesson(&mut self, index: usize) {
+        self.lesson_selector.select(index);
+    }
+
     pub fn render_menu(&self, f: &mut Frame) {
         renderer::render_menu(f, &self.menu);
     }
 
     pub fn render_file_select(&self, f: &mut Frame) {
-        renderer::render_file_select(f, self.file_selector.input(), self.file_selector.error());
+        renderer::render_file_select(
+            f,
+            self.file_selector.input(),
+            self.file_selector.difficulty(),
+            self.file_selector.spawn_rule(),
+            self.file_selector.error(),
+        );
+    }
+
+    pub fn render_drill_select(&self, f: &mut Frame) {
+        renderer::render_drill_select(f, &self.drill_selector);
+    }
+
+    pub fn render_challenge_select(&self, f: &mut Frame) {
+        renderer::render_challenge_select(f, &self.challenge_selector);
+    }
+
+    pub fn render_replay_select(&self, f: &mut Frame) {
+        renderer::render_replay_select(f, &self.replay_selector);
+    }
+
+    pub fn render_high_scores(&self, f: &mut Frame, scores: &HighScores) {
+        renderer::render_high_scores(f, &self.high_score_browser, scores);
+    }
+
+    pub fn render_results(&self, f: &mut Frame, results: ResultsView) {
+        renderer::render_results(f, results);
+    }
+
+    pub fn render_challenge<'a>(
+        &self,
+        f: &mut Frame,
+        run: &ChallengeRun,
+        inserting: bool,
+        keys_iter: impl Iterator<Item = &'a KeyEvent>,
+    ) {
+        renderer::render_challenge(f, run, inserting, keys_iter);
+    }
+
+    pub fn render_lesson_select(&self, f: &mut Frame, progress: &LessonProgress) {
+        renderer::render_lesson_select(f, &self.lesson_selector, progress);
+    }
+
+    pub fn render_lesson<'a>(
+        &self,
+        f: &mut Frame,
+        lesson: LessonView,
+        keys_iter: impl Iterator<Item = &'a KeyEvent>,
+    ) {
+        renderer::render_lesson(f, lesson, keys_iter);
     }
 
     pub fn render_game<'a>(
         &mut self,
         f: &mut Frame,
         game: &World,
-        events: &[GameEvent],
+        stats: &RoundStats,
         keys_iter: impl Iterator<Item = &'a KeyEvent>,
+        ghost: Option<GhostView>,
     ) {
-        // Handle world events
-        self.handle_events(events);
+        self.render_round(f, game, stats, keys_iter, ghost, f.area());
+    }
 
+    /// Renders a round played back from a replay, under a bar showing the playback.
+    pub fn render_replay<'a>(
+        &mut self,
+        f: &mut Frame,
+        game: &World,
+        stats: &RoundStats,
+        keys_iter: impl Iterator<Item = &'a KeyEvent>,
+        replay: ReplayView,
+    ) {
         let chunks = Layout::default()
-            .constraints([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+            .constraints([Constraint::Length(REPLAY_BAR_HEIGHT), Constraint::Min(0)])
             .split(f.area());
+        renderer::render_replay_bar(f, replay, chunks[0]);
+        self.render_round(f, game, stats, keys_iter, None, chunks[1]);
+    }
+
+    /// Exports the world and status bar as last drawn, on a `width` × `height` terminal.
+    pub fn screenshot<'a>(
+        &self,
+        game: &World,
+        stats: &RoundStats,
+        keys_iter: impl Iterator<Item = &'a KeyEvent>,
+        width: u16,
+        height: u16,
+        format: ScreenshotFormat,
+    ) -> String {
+        let frame = screenshot::capture(width, height, |f| {
+            let chunks = Layout::default()
+                .constraints([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+                .split(f.area());
+            let mut viewport = self.viewport;
+            let visible_height = chunks[0].height.saturating_sub(2) as usize;
+            viewport.adjust_for_cursor(game.cursor().pos(), game.buffer_lines(), visible_height);
+
+            let feedback = self.feedback.borrow();
+            let hint = feedback.hint.as_ref();
+            let effects = self.effects.borrow();
+            renderer::render_world(f, game, &effects, hint, None, &viewport, chunks[0]);
+            let status = StatusView {
+                stats,
+                hint,
+                rejection: None,
+                ghost_score: None,
+            };
+            renderer::render_status_bar(f, game, status, keys_iter, chunks[1]);
+        });
+        screenshot::export(&frame, format)
+    }
+
+    /// Shows a short message in the corner of the round, like score popups.
+    pub fn notify(&mut self, message: String) {
+        self.feedback.borrow_mut().notify(message, self.clock.now());
+    }
+
+    /// Returns true while effects or popups are still fading out.
+    pub fn is_animating(&self) -> bool {
+        !self.effects.borrow().is_empty() || !self.feedback.borrow().popups.is_empty()
+    }
+
+    /// Renders the world and status bar of a round within `area`.
+    fn render_round<'a>(
+        &mut self,
+        f: &mut Frame,
+        game: &World,
+        stats: &RoundStats,
+        keys_iter: impl Iterator<Item = &'a KeyEvent>,
+        ghost: Option<GhostView>,
+        area: Rect,
+    ) {
+        let now = self.clock.now();
+        let chunks = Layout::default()
+            .constraints([Constraint::Min(0), Constraint::Length(STATUS_BAR_HEIGHT)])
+            .split(area);
 
         // Update viewport based on available height (subtract 2 for borders)
         let visible_height = chunks[0].height.saturating_sub(2) as usize;
@@ -89,28 +339,104 @@ impl UiManager {
         self.viewport
             .adjust_for_cursor(game.cursor().pos(), game.buffer_lines(), visible_height);
 
-        renderer::render_world(f, game, &self.effects, &self.viewport, chunks[0]);
-        renderer::render_status_bar(f, game, keys_iter, chunks[1]);
+        let mut feedback = self.feedback.borrow_mut();
+        let mut effects = self.effects.borrow_mut();
+        let hint = feedback.hint.as_ref();
+        let ghost_cursor = ghost.as_ref().map(|g| g.cursor);
+        renderer::render_world(
+            f,
+            game,
+            &effects,
+            hint,
+            ghost_cursor,
+            &self.viewport,
+            chunks[0],
+        );
+        let rejection = feedback
+            .rejection
+            .as_ref()
+            .filter(|(_, at)| now.duration_since(*at) < REJECTED_FEEDBACK_DURATION)
+            .map(|(message, _)| message.as_str());
+        let status = StatusView {
+            stats,
+            hint,
+            rejection,
+            ghost_score: ghost.map(|g| g.score),
+        };
+        renderer::render_status_bar(f, game, status, keys_iter, chunks[1]);
+
+        feedback
+            .popups
+            .retain(|(_, at)| now.duration_since(*at) < SCORE_POPUP_DURATION);
+        renderer::render_score_popups(f, feedback.popups.iter().map(|(text, _)| text), chunks[0]);
 
         // Cleanup expired effects
-        self.effects.cleanup();
-    }
-
-    fn handle_events(&mut self, events: &[GameEvent]) {
-        // Process events and spawn visual effects
-        for event in events {
-            match event {
-                GameEvent::EnemyDestroyed { position } => {
-                    self.effects.spawn_effect(Effect::collision(*position));
-                }
-                GameEvent::CursorMoved {
-                    position,
-                    timestamp,
-                } => {
-                    self.effects
-                        .spawn_effect(Effect::trailing(*position, *timestamp));
-                }
+        effects.cleanup(now);
+    }
+}
+
+/// Hint, score popups and drill feedback drawn over a round.
+#[derive(Default)]
+struct Feedback {
+    /// Hint currently drawn over the buffer
+    hint: Option<Route>,
+    /// Why the last motion was rejected by the drill or the last hint refused, and when
+    rejection: Option<(String, Instant)>,
+    /// Recent score popups, newest last, with when they appeared
+    popups: VecDeque<(String, Instant)>,
+}
+
+impl Feedback {
+    /// Shows a short message in the corner of the round, dropping the oldest past the limit.
+    fn notify(&mut self, message: String, now: Instant) {
+        self.popups.push_back((message, now));
+        if self.popups.len() > MAX_SCORE_POPUPS {
+            self.popups.pop_front();
+        }
+    }
+}
+
+impl EventSubscriber for Feedback {
+    /// Keeps the hint, popups and drill feedback in step with the round.
+    fn on_event(&mut self, event: &GameEvent, now: Instant) {
+        match event {
+            // The hinted enemy is gone
+            GameEvent::EnemyDestroyed { position }
+                if self.hint.as_ref().is_some_and(|h| h.target == *position) =>
+            {
+                self.hint = None;
+            }
+            GameEvent::ScoreAwarded {
+                base,
+                multiplier,
+                reason,
+            } => {
+                let text = if *multiplier > 1 {
+                    format!("+{} ×{} {}", base, multiplier, reason)
+                } else {
+                    format!("+{} {}", base, reason)
+                };
+                self.notify(text, now);
+            }
+            // Keep the hint only while the player follows it
+            GameEvent::CursorMoved { position, .. }
+                if self
+                    .hint
+                    .as_ref()
+                    .is_some_and(|h| !h.path.contains(position)) =>
+            {
+                self.hint = None;
+            }
+            GameEvent::HintShown { route, .. } => {
+                self.hint = Some(route.clone());
+            }
+            GameEvent::MotionRejected { violation } => {
+                self.rejection = Some((violation.to_string(), now));
+            }
+            GameEvent::HintRefused { reason } => {
+                self.rejection = Some((reason.to_string(), now));
             }
+            _ => {}
         }
     }
 }